@@ -2,57 +2,141 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::bet::{bet::Bet, bet_state::BetState};
+use crate::roulette::game_logs::GameLog;
 
-use super::agent_log::AgentLog;
+use super::{
+    agent_exit::AgentExit, agent_log::AgentLog, exit_reason::ExitReason, exit_rules::ExitRules,
+};
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Deserialize, Serialize)]
 pub struct Agent {
     pub balance_cents: i64,
     pub strategic_bets: Vec<Bet>,
     pub name: String,
     pub agent_logs: Vec<AgentLog>,
+    pub exit_rules: ExitRules,
+    pub starting_balance_cents: i64,
+    pub peak_balance_cents: i64,
+    pub exit: Option<AgentExit>,
 }
 
 impl Agent {
+    pub fn start_session(&mut self) {
+        self.starting_balance_cents = self.balance_cents;
+        self.peak_balance_cents = self.balance_cents;
+        self.exit = None;
+    }
+
+    pub fn has_exited(&self) -> bool {
+        return self.exit.is_some();
+    }
+
+    /// Whether every bet the agent still plays had to sit out for lack of funds
+    pub fn is_out_of_funds(&self) -> bool {
+        let mut live_bets = self
+            .strategic_bets
+            .iter()
+            .filter(|bet| bet.bet_state != BetState::Retired && bet.bet_state != BetState::Waiting)
+            .peekable();
+        return live_bets.peek().is_some()
+            && live_bets.all(|bet| bet.bet_state == BetState::Inactive);
+    }
+
+    /// First round the agent went broke, its balance gone or no bet affordable
+    pub fn ruin_round(&self) -> Option<i32> {
+        return self
+            .agent_logs
+            .iter()
+            .find(|agent_log| agent_log.balance_cents <= 0 || agent_log.out_of_funds)
+            .map(|agent_log| agent_log.round_number);
+    }
+
+    /// Largest fall of the balance from its highest point so far, over the session
+    pub fn max_drawdown_cents(&self) -> i64 {
+        let mut peak_balance_cents = self.starting_balance_cents;
+        let mut max_drawdown_cents = 0;
+        for agent_log in self.agent_logs.iter() {
+            peak_balance_cents = peak_balance_cents.max(agent_log.balance_cents);
+            max_drawdown_cents =
+                max_drawdown_cents.max(peak_balance_cents - agent_log.balance_cents);
+        }
+        return max_drawdown_cents;
+    }
+
+    pub fn exit_reason(&self) -> ExitReason {
+        return self
+            .exit
+            .map(|exit| exit.exit_reason)
+            .unwrap_or(ExitReason::Completed);
+    }
+
+    /// Walks away from the table once one of the exit rules is met after `round_number`.
+    pub fn check_exit_rules(&mut self, round_number: i32) {
+        if self.has_exited() {
+            return;
+        }
+        self.peak_balance_cents = self.peak_balance_cents.max(self.balance_cents);
+        if let Some(exit_reason) = self.exit_rules.check(
+            self.starting_balance_cents,
+            self.peak_balance_cents,
+            self.balance_cents,
+            round_number,
+        ) {
+            self.exit = Some(AgentExit {
+                round_number,
+                exit_reason,
+            });
+        }
+    }
+
+    /// Takes every bet off the table. A bet held by En Prison can't be taken
+    /// along, its stake is forfeited as if it had lost its next spin.
+    pub fn leave_table(&mut self) {
+        self.strategic_bets.iter_mut().for_each(|bet| {
+            bet.bet_state = match bet.bet_state {
+                BetState::Imprisoned => BetState::Forfeited,
+                _ => BetState::Inactive,
+            };
+        });
+    }
+
     pub fn consolidate_bets(&mut self) {
-        let mut bets: HashMap<String, HashMap<String, Bet>> = HashMap::new();
+        // keeps the first-seen order so affordability checks are reproducible
+        let mut bet_indices: HashMap<String, usize> = HashMap::new();
         let mut consolidated_bets: Vec<Bet> = Vec::new();
 
-        for bet in self.strategic_bets.iter_mut() {
-            let bet_type_string = bet.bet_value.get_type();
-            bets.entry(bet_type_string)
-                .and_modify(|type_to_bets| {
-                    type_to_bets
-                        .entry(bet_hash(bet))
-                        .and_modify(|existing_bet| {
-                            existing_bet.initial_amount_cents += bet.initial_amount_cents;
-                            existing_bet.amount_cents += bet.amount_cents;
-                        })
-                        .or_insert(bet.clone());
-                })
-                .or_insert_with(|| {
-                    let mut type_to_bets: HashMap<String, Bet> = HashMap::new();
-                    type_to_bets.insert(bet_hash(bet), bet.clone());
-                    return type_to_bets;
-                });
-        }
-        for (_, type_to_bet) in bets {
-            for (_, bet) in type_to_bet {
-                consolidated_bets.push(bet);
+        for bet in self.strategic_bets.iter() {
+            match bet_indices.get(&bet_hash(bet)) {
+                Some(&index) => {
+                    let existing_bet = &mut consolidated_bets[index];
+                    existing_bet.initial_amount_cents += bet.initial_amount_cents;
+                    existing_bet.amount_cents += bet.amount_cents;
+                }
+                None => {
+                    bet_indices.insert(bet_hash(bet), consolidated_bets.len());
+                    consolidated_bets.push(bet.clone());
+                }
             }
         }
         self.strategic_bets = consolidated_bets;
     }
 
     pub fn allow_all_bets(&mut self) {
-        self.strategic_bets.iter_mut().for_each(|bet| {
-            bet.bet_state = BetState::Active;
-        });
+        self.strategic_bets
+            .iter_mut()
+            .filter(|bet| !bet.bet_state.is_locked())
+            .for_each(|bet| {
+                bet.bet_state = BetState::Active;
+            });
     }
 
     pub fn determine_affordable_bets(&mut self) {
         let mut total_bet_value = 0;
-        for bet in self.strategic_bets.iter_mut() {
+        for bet in self
+            .strategic_bets
+            .iter_mut()
+            .filter(|bet| !bet.bet_state.is_locked())
+        {
             total_bet_value += bet.amount_cents;
             if total_bet_value <= self.balance_cents {
                 bet.bet_state = BetState::Active;
@@ -62,24 +146,26 @@ impl Agent {
         }
     }
 
-    pub fn play_strategy(&mut self) {
+    pub fn play_strategy(&mut self, game_logs: &[GameLog]) {
+        let balance_cents = self.balance_cents;
         for bet in self.strategic_bets.iter_mut() {
-            if bet.bet_state == BetState::Lost {
-                bet.amount_cents *= bet.progression_factor;
-            } else if bet.bet_state == BetState::Won {
-                bet.amount_cents = bet.initial_amount_cents;
-            }
+            bet.play_strategy(balance_cents, game_logs);
         }
     }
 }
 
 fn bet_hash(bet: &Bet) -> String {
-    return format!("{:?} {:?}", bet.bet_value, bet.progression_factor);
+    return format!(
+        "{:?} {:?} {:?} {:?}",
+        bet.bet_value, bet.progression_factor, bet.strategy, bet.trigger
+    );
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{bet::bet_value::BetValue, types::color::Color};
+    use crate::{
+        bet::bet_value::BetValue, strategy::strategy_type::StrategyType, types::color::Color,
+    };
 
     use super::*;
     #[test]
@@ -94,7 +180,11 @@ mod test {
                     bet_state: BetState::Active,
                     bet_value: BetValue::Number(1),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
                 Bet {
                     amount_cents: 1000,
@@ -102,10 +192,15 @@ mod test {
                     bet_state: BetState::Active,
                     bet_value: BetValue::Number(1),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
             ],
             agent_logs: Vec::new(),
+            ..Default::default()
         };
 
         agent.consolidate_bets();
@@ -113,6 +208,53 @@ mod test {
         assert_eq!(agent.strategic_bets[0].amount_cents, 2000);
     }
 
+    #[test]
+    fn test_consolidate_bets_keeps_order() {
+        let bet_values = vec![
+            BetValue::Number(5),
+            BetValue::Color(Color::Red),
+            BetValue::Number(1),
+            BetValue::Color(Color::Red),
+        ];
+        let mut agent = Agent {
+            balance_cents: 100000,
+            name: String::from("Test Agent"),
+            strategic_bets: bet_values
+                .into_iter()
+                .map(|bet_value| Bet {
+                    amount_cents: 1000,
+                    bet_logs: Vec::new(),
+                    bet_state: BetState::Active,
+                    bet_value,
+                    initial_amount_cents: 1000,
+                    payout_cents: 0,
+                    progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
+                })
+                .collect(),
+            agent_logs: Vec::new(),
+            ..Default::default()
+        };
+
+        agent.consolidate_bets();
+        let bet_values: Vec<BetValue> = agent
+            .strategic_bets
+            .iter()
+            .map(|bet| bet.bet_value.clone())
+            .collect();
+        assert_eq!(
+            bet_values,
+            vec![
+                BetValue::Number(5),
+                BetValue::Color(Color::Red),
+                BetValue::Number(1)
+            ]
+        );
+        assert_eq!(agent.strategic_bets[1].amount_cents, 2000);
+    }
+
     #[test]
     fn test_allow_all_bets() {
         let mut agent = Agent {
@@ -125,7 +267,11 @@ mod test {
                     bet_state: BetState::Inactive,
                     bet_value: BetValue::Number(1),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
                 Bet {
                     amount_cents: 1000,
@@ -133,10 +279,15 @@ mod test {
                     bet_state: BetState::Active,
                     bet_value: BetValue::Color(Color::Black),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
             ],
             agent_logs: Vec::new(),
+            ..Default::default()
         };
 
         agent.allow_all_bets();
@@ -157,7 +308,11 @@ mod test {
                     bet_state: BetState::Inactive,
                     bet_value: BetValue::Number(1),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
                 Bet {
                     amount_cents: 1000,
@@ -165,10 +320,15 @@ mod test {
                     bet_state: BetState::Active,
                     bet_value: BetValue::Color(Color::Black),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
             ],
             agent_logs: Vec::new(),
+            ..Default::default()
         };
 
         agent.allow_all_bets();
@@ -189,7 +349,11 @@ mod test {
                     bet_state: BetState::Won,
                     bet_value: BetValue::Number(1),
                     initial_amount_cents: 3500,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
                 Bet {
                     amount_cents: 5000,
@@ -197,13 +361,18 @@ mod test {
                     bet_state: BetState::Lost,
                     bet_value: BetValue::Color(Color::Black),
                     initial_amount_cents: 5000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 },
             ],
             agent_logs: Vec::new(),
+            ..Default::default()
         };
 
-        agent.play_strategy();
+        agent.play_strategy(&[]);
         assert_eq!(agent.strategic_bets.len(), 2);
         let sum_of_amounts = agent
             .strategic_bets
@@ -212,4 +381,65 @@ mod test {
             .sum::<i64>();
         assert_eq!(sum_of_amounts, 13500);
     }
+
+    #[test]
+    fn test_imprisoned_bets_stay_on_the_table() {
+        let mut agent = Agent {
+            balance_cents: 500,
+            name: String::from("Test Agent"),
+            strategic_bets: vec![Bet {
+                amount_cents: 1000,
+                bet_logs: Vec::new(),
+                bet_state: BetState::Imprisoned,
+                bet_value: BetValue::Color(Color::Black),
+                initial_amount_cents: 1000,
+                payout_cents: 0,
+                progression_factor: 2,
+                strategy: StrategyType::default(),
+                trigger: None,
+                table_limit_hit: false,
+            }],
+            agent_logs: Vec::new(),
+            ..Default::default()
+        };
+
+        agent.determine_affordable_bets();
+        assert_eq!(agent.strategic_bets[0].bet_state, BetState::Imprisoned);
+        agent.allow_all_bets();
+        assert_eq!(agent.strategic_bets[0].bet_state, BetState::Imprisoned);
+        agent.play_strategy(&[]);
+        assert_eq!(agent.strategic_bets[0].amount_cents, 1000);
+    }
+
+    #[test]
+    fn test_leave_table_forfeits_imprisoned_bets() {
+        let create_bet = |bet_state: BetState| Bet {
+            amount_cents: 1000,
+            bet_logs: Vec::new(),
+            bet_state,
+            bet_value: BetValue::Color(Color::Black),
+            initial_amount_cents: 1000,
+            payout_cents: 0,
+            progression_factor: 2,
+            strategy: StrategyType::default(),
+            trigger: None,
+            table_limit_hit: false,
+        };
+        let mut agent = Agent {
+            balance_cents: 5000,
+            name: String::from("Test Agent"),
+            strategic_bets: vec![create_bet(BetState::Imprisoned), create_bet(BetState::Lost)],
+            agent_logs: Vec::new(),
+            ..Default::default()
+        };
+
+        agent.leave_table();
+        assert_eq!(agent.strategic_bets[0].bet_state, BetState::Forfeited);
+        assert!(agent.strategic_bets[0].bet_state.is_loss());
+        assert_eq!(agent.strategic_bets[1].bet_state, BetState::Inactive);
+        assert_eq!(agent.balance_cents, 5000);
+        // once forfeited the bet stays off the table like the others
+        agent.leave_table();
+        assert_eq!(agent.strategic_bets[0].bet_state, BetState::Inactive);
+    }
 }
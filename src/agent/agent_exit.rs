@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use super::exit_reason::ExitReason;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub struct AgentExit {
+    pub round_number: i32,
+    pub exit_reason: ExitReason,
+}
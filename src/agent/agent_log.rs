@@ -1,7 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+use super::exit_reason::ExitReason;
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct AgentLog {
     pub round_number: i32,
     pub balance_cents: i64,
+    /// Set on the round the agent walked away from the table
+    pub exit_reason: Option<ExitReason>,
+    /// Set on rounds the agent was at the table but couldn't afford any of its bets
+    pub out_of_funds: bool,
 }
@@ -0,0 +1,26 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub enum ExitReason {
+    /// Played every round of the game
+    Completed,
+    StopLoss,
+    TakeProfit,
+    MaxSpins,
+    TrailingStop,
+}
+
+impl fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ExitReason::Completed => "Completed",
+            ExitReason::StopLoss => "Stop Loss",
+            ExitReason::TakeProfit => "Take Profit",
+            ExitReason::MaxSpins => "Max Spins",
+            ExitReason::TrailingStop => "Trailing Stop",
+        };
+        write!(f, "{}", s)
+    }
+}
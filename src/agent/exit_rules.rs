@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use super::exit_reason::ExitReason;
+use crate::json::validation_report::ValidationIssue;
+
+/// Conditions under which an agent walks away from the table. Amounts are
+/// relative to the balance the agent sat down with, except the trailing stop
+/// which follows the highest balance reached so far.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct ExitRules {
+    pub stop_loss_cents: Option<i64>,
+    pub take_profit_cents: Option<i64>,
+    pub max_spins: Option<i32>,
+    pub trailing_stop_cents: Option<i64>,
+}
+
+impl ExitRules {
+    /// Every rule that is set must be positive, a rule of zero or less would
+    /// end the session before its first spin
+    pub fn find_issues(&self) -> Vec<ValidationIssue> {
+        return [
+            ("stop_loss_cents", self.stop_loss_cents),
+            ("take_profit_cents", self.take_profit_cents),
+            ("max_spins", self.max_spins.map(i64::from)),
+            ("trailing_stop_cents", self.trailing_stop_cents),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| match value {
+            Some(value) if value <= 0 => Some(ValidationIssue::new(
+                field,
+                &format!("Must be positive, got {}", value),
+            )),
+            _ => None,
+        })
+        .collect();
+    }
+
+    /// Returns the first rule triggered after `spins_played` spins, checking
+    /// losses before wins so a session never ends on the more flattering reason.
+    pub fn check(
+        &self,
+        starting_balance_cents: i64,
+        peak_balance_cents: i64,
+        balance_cents: i64,
+        spins_played: i32,
+    ) -> Option<ExitReason> {
+        if let Some(stop_loss_cents) = self.stop_loss_cents {
+            if balance_cents <= starting_balance_cents.saturating_sub(stop_loss_cents) {
+                return Some(ExitReason::StopLoss);
+            }
+        }
+        if let Some(trailing_stop_cents) = self.trailing_stop_cents {
+            if balance_cents <= peak_balance_cents.saturating_sub(trailing_stop_cents) {
+                return Some(ExitReason::TrailingStop);
+            }
+        }
+        if let Some(take_profit_cents) = self.take_profit_cents {
+            if balance_cents >= starting_balance_cents.saturating_add(take_profit_cents) {
+                return Some(ExitReason::TakeProfit);
+            }
+        }
+        if let Some(max_spins) = self.max_spins {
+            if spins_played >= max_spins {
+                return Some(ExitReason::MaxSpins);
+            }
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_rules_never_exit() {
+        assert_eq!(ExitRules::default().check(1000, 5000, -3000, 1000), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let exit_rules = ExitRules {
+            stop_loss_cents: Some(500),
+            take_profit_cents: Some(1000),
+            max_spins: Some(10),
+            trailing_stop_cents: Some(700),
+        };
+        assert_eq!(exit_rules.check(1000, 1000, 900, 1), None);
+        assert_eq!(
+            exit_rules.check(1000, 1000, 500, 1),
+            Some(ExitReason::StopLoss)
+        );
+        assert_eq!(
+            exit_rules.check(1000, 1800, 1100, 1),
+            Some(ExitReason::TrailingStop)
+        );
+        assert_eq!(
+            exit_rules.check(1000, 2000, 2000, 1),
+            Some(ExitReason::TakeProfit)
+        );
+        assert_eq!(
+            exit_rules.check(1000, 1000, 1000, 10),
+            Some(ExitReason::MaxSpins)
+        );
+    }
+
+    #[test]
+    fn test_check_huge_amounts() {
+        let exit_rules = ExitRules {
+            stop_loss_cents: Some(i64::MAX),
+            take_profit_cents: Some(i64::MAX),
+            max_spins: None,
+            trailing_stop_cents: Some(i64::MAX),
+        };
+        assert_eq!(exit_rules.check(-1000, 1000, -5000, 1), None);
+        assert_eq!(exit_rules.check(1000, 1000, i64::MAX - 1, 1), None);
+    }
+
+    #[test]
+    fn test_find_issues() {
+        assert!(ExitRules::default().find_issues().is_empty());
+        let exit_rules = ExitRules {
+            stop_loss_cents: Some(0),
+            take_profit_cents: Some(1000),
+            max_spins: Some(-5),
+            trailing_stop_cents: Some(-100),
+        };
+        let fields: Vec<String> = exit_rules
+            .find_issues()
+            .into_iter()
+            .map(|issue| issue.path)
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["stop_loss_cents", "max_spins", "trailing_stop_cents"]
+        );
+    }
+}
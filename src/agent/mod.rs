@@ -1,2 +1,6 @@
+#[allow(clippy::module_inception)]
 pub mod agent;
+pub mod agent_exit;
 pub mod agent_log;
+pub mod exit_reason;
+pub mod exit_rules;
@@ -1,9 +1,20 @@
-use crate::json::json_reader::JsonReader;
+use crate::json::{json_reader::JsonReader, load_options::LoadOptions};
 use crate::roulette::stats::Stats;
 
-use actix_web::{get, HttpResponse, Responder};
+use std::time::Instant;
+
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse, Responder};
+use actix_ws::Message;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{error, info};
 
+use super::{
+    live_filter::LiveFilter,
+    simulation_jobs::{JobKind, JobStatus, SimulationJobs},
+};
+
 use crate::roulette::game_runner::run;
 
 const GAME_CONFIG_FILENAME: &str = "./res/game.json";
@@ -11,33 +22,26 @@ const AGENTS_FILENAME: &str = "./res/agents.json";
 
 #[get("/test")]
 async fn run_with_files() -> impl Responder {
-    let game_config_res = JsonReader::read_game_json(GAME_CONFIG_FILENAME);
-    let agents_res = JsonReader::read_agents_json(AGENTS_FILENAME);
-    let game_config;
-    let agents;
-    if game_config_res.is_ok() {
-        game_config = game_config_res.unwrap();
-    } else {
-        error!(
-            "Failed to read game config: {}, path: {}",
-            game_config_res.err().unwrap(),
-            GAME_CONFIG_FILENAME
-        );
-        return HttpResponse::InternalServerError().body("Failed to read game config");
-    }
-    if agents_res.is_ok() {
-        agents = agents_res.unwrap();
-    } else {
-        error!(
-            "Failed to read agents: {}, path: {}",
-            agents_res.err().unwrap(),
-            AGENTS_FILENAME
-        );
-        return HttpResponse::InternalServerError().body("Failed to read agents");
-    }
+    let game_config = match JsonReader::read_game_json(GAME_CONFIG_FILENAME) {
+        Ok(game_config) => game_config,
+        Err(err) => {
+            error!(
+                "Failed to read game config: {}, path: {}",
+                err, GAME_CONFIG_FILENAME
+            );
+            return HttpResponse::InternalServerError().body("Failed to read game config");
+        }
+    };
+    let agents = match JsonReader::read_agents_json(AGENTS_FILENAME) {
+        Ok(agents) => agents,
+        Err(err) => {
+            error!("Failed to read agents: {}, path: {}", err, AGENTS_FILENAME);
+            return HttpResponse::InternalServerError().body("Failed to read agents");
+        }
+    };
     match run(game_config, agents).await {
         Ok(result) => {
-            let stats: Stats = Stats::from_games(&result);
+            let stats: Stats = result.stats;
             match serde_json::to_string(&stats) {
                 Ok(json_string) => {
                     info!("Stats: {}", stats);
@@ -55,3 +59,263 @@ async fn run_with_files() -> impl Responder {
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+struct SimulationRequest {
+    game_config: Value,
+    agents: Value,
+    /// Rejects the agents if any bet is invalid instead of leaving it out
+    #[serde(default)]
+    strict: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SweepRequest {
+    game_config: Value,
+    agents: Value,
+    sweep: Value,
+    /// Rejects the agents if any bet is invalid instead of leaving it out
+    #[serde(default)]
+    strict: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SweepQuery {
+    /// `csv` for the rows of a completed sweep as a CSV body, the job as JSON otherwise
+    format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidationRequest {
+    /// Bets are checked against the wheel types of this config, European without one
+    game_config: Option<Value>,
+    agents: Value,
+}
+
+#[post("/simulations")]
+async fn create_simulation(
+    simulation_jobs: web::Data<SimulationJobs>,
+    request: web::Json<SimulationRequest>,
+) -> impl Responder {
+    let request = request.into_inner();
+    let game_config = match JsonReader::parse_game_config(request.game_config) {
+        Ok(game_config) => game_config,
+        Err(err) => {
+            return HttpResponse::BadRequest().body(format!("Invalid game config: {}", err));
+        }
+    };
+    let load_options = LoadOptions::for_game(&game_config, request.strict);
+    let agents = match JsonReader::parse_agents_with(request.agents, &load_options) {
+        Ok(agents) => agents,
+        Err(err) => {
+            return HttpResponse::BadRequest().body(format!("Invalid agents: {}", err));
+        }
+    };
+    let job = simulation_jobs.submit(game_config, agents);
+    return HttpResponse::Accepted().json(job);
+}
+
+/// Queues a job that runs the agents for every point of the sweep
+#[post("/sweeps")]
+async fn create_sweep(
+    simulation_jobs: web::Data<SimulationJobs>,
+    request: web::Json<SweepRequest>,
+) -> impl Responder {
+    let request = request.into_inner();
+    let game_config = match JsonReader::parse_game_config(request.game_config) {
+        Ok(game_config) => game_config,
+        Err(err) => {
+            return HttpResponse::BadRequest().body(format!("Invalid game config: {}", err));
+        }
+    };
+    let sweep_config = match JsonReader::parse_sweep_config(request.sweep, &game_config) {
+        Ok(sweep_config) => sweep_config,
+        Err(err) => {
+            return HttpResponse::BadRequest().body(format!("Invalid sweep: {}", err));
+        }
+    };
+    let load_options = LoadOptions::for_game(&game_config, request.strict);
+    let agents = match JsonReader::parse_agents_with(request.agents, &load_options) {
+        Ok(agents) => agents,
+        Err(err) => {
+            return HttpResponse::BadRequest().body(format!("Invalid agents: {}", err));
+        }
+    };
+    let job = simulation_jobs.submit_sweep(game_config, agents, sweep_config);
+    return HttpResponse::Accepted().json(job);
+}
+
+#[get("/sweeps/{id}")]
+async fn get_sweep(
+    simulation_jobs: web::Data<SimulationJobs>,
+    id: web::Path<String>,
+    query: web::Query<SweepQuery>,
+) -> impl Responder {
+    let job = match simulation_jobs.get(&id, JobKind::Sweep) {
+        Some(job) => job,
+        None => return HttpResponse::NotFound().body(format!("No sweep with id {}", id)),
+    };
+    if query.format.as_deref() != Some("csv") {
+        return HttpResponse::Ok().json(job);
+    }
+    return match &job.sweep_result {
+        Some(sweep_result) => HttpResponse::Ok()
+            .content_type("text/csv")
+            .body(sweep_result.to_csv()),
+        None => HttpResponse::Conflict().body(format!(
+            "Sweep {} is {}, its rows are ready once it has completed",
+            id, job.status
+        )),
+    };
+}
+
+#[delete("/sweeps/{id}")]
+async fn cancel_sweep(
+    simulation_jobs: web::Data<SimulationJobs>,
+    id: web::Path<String>,
+) -> impl Responder {
+    return match simulation_jobs.cancel(&id, JobKind::Sweep) {
+        Some(job) if job.status == JobStatus::Cancelled => HttpResponse::Ok().json(job),
+        Some(job) => HttpResponse::Conflict().json(job),
+        None => HttpResponse::NotFound().body(format!("No sweep with id {}", id)),
+    };
+}
+
+/// Reports every problem of the agents without running anything
+#[post("/validate")]
+async fn validate_agents(request: web::Json<ValidationRequest>) -> impl Responder {
+    let request = request.into_inner();
+    let roulette_types = match request.game_config {
+        Some(game_config) => match JsonReader::parse_game_config(game_config) {
+            Ok(game_config) => game_config.get_roulette_types(),
+            Err(err) => {
+                return HttpResponse::BadRequest().body(format!("Invalid game config: {}", err));
+            }
+        },
+        None => LoadOptions::default().roulette_types,
+    };
+    let report = JsonReader::validate_agents(&request.agents, &roulette_types);
+    return HttpResponse::Ok().json(report);
+}
+
+#[get("/simulations/{id}")]
+async fn get_simulation(
+    simulation_jobs: web::Data<SimulationJobs>,
+    id: web::Path<String>,
+) -> impl Responder {
+    return match simulation_jobs.get(&id, JobKind::Simulation) {
+        Some(job) => HttpResponse::Ok().json(job),
+        None => HttpResponse::NotFound().body(format!("No simulation with id {}", id)),
+    };
+}
+
+#[derive(Debug, Deserialize)]
+struct TrajectoryQuery {
+    /// Cuts every trajectory down to this many rounds
+    points: Option<usize>,
+}
+
+/// Balance trajectories of a completed job, per agent
+#[get("/simulations/{id}/trajectories")]
+async fn get_simulation_trajectories(
+    simulation_jobs: web::Data<SimulationJobs>,
+    id: web::Path<String>,
+    query: web::Query<TrajectoryQuery>,
+) -> impl Responder {
+    let job = match simulation_jobs.get(&id, JobKind::Simulation) {
+        Some(job) => job,
+        None => return HttpResponse::NotFound().body(format!("No simulation with id {}", id)),
+    };
+    return match &job.stats {
+        Some(stats) => HttpResponse::Ok().json(stats.get_balance_trajectories(query.points)),
+        None => HttpResponse::Conflict().body(format!(
+            "Simulation {} is {}, trajectories are ready once it has completed",
+            id, job.status
+        )),
+    };
+}
+
+#[delete("/simulations/{id}")]
+async fn cancel_simulation(
+    simulation_jobs: web::Data<SimulationJobs>,
+    id: web::Path<String>,
+) -> impl Responder {
+    return match simulation_jobs.cancel(&id, JobKind::Simulation) {
+        Some(job) if job.status == JobStatus::Cancelled => HttpResponse::Ok().json(job),
+        Some(job) => HttpResponse::Conflict().json(job),
+        None => HttpResponse::NotFound().body(format!("No simulation with id {}", id)),
+    };
+}
+
+/// Streams the rounds of a running job as JSON text messages, narrowed down
+/// by the `game_number`, `roulette_type`, `even_money_rule`, `agent_name` and
+/// `interval_ms` query parameters.
+/// Pings are answered and a close from the client ends the stream.
+#[get("/simulations/{id}/live")]
+async fn stream_simulation(
+    request: HttpRequest,
+    body: web::Payload,
+    simulation_jobs: web::Data<SimulationJobs>,
+    id: web::Path<String>,
+    live_filter: web::Query<LiveFilter>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let mut round_events = match simulation_jobs.subscribe(&id) {
+        Some(round_events) => round_events,
+        None => {
+            return Ok(
+                HttpResponse::NotFound().body(format!("No running simulation with id {}", id))
+            )
+        }
+    };
+    let live_filter = live_filter.into_inner();
+    let (response, mut session, mut messages) = actix_ws::handle(&request, body)?;
+    actix_web::rt::spawn(async move {
+        let mut last_sent: Option<Instant> = None;
+        loop {
+            let round_event = tokio::select! {
+                message = messages.recv() => match message {
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+                    Some(Ok(Message::Close(reason))) => {
+                        let _ = session.close(reason).await;
+                        return;
+                    }
+                    Some(Ok(_)) => continue,
+                    // the client went away
+                    Some(Err(_)) | None => return,
+                },
+                round_event = round_events.recv() => match round_event {
+                    Ok(round_event) => round_event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        info!("Live stream fell behind, skipped {} rounds", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+            };
+            let now = Instant::now();
+            let round_event = match live_filter.apply(round_event) {
+                Some(round_event) if live_filter.is_due(last_sent, now) => round_event,
+                _ => continue,
+            };
+            let message = match serde_json::to_string(&round_event) {
+                Ok(message) => message,
+                Err(err) => {
+                    error!("Failed to convert round event to json: {}", err);
+                    continue;
+                }
+            };
+            if session.text(message).await.is_err() {
+                // the client went away
+                return;
+            }
+            last_sent = Some(now);
+        }
+        let _ = session.close(None).await;
+    });
+    return Ok(response);
+}
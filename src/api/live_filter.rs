@@ -0,0 +1,210 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::roulette::{
+    even_money_rule::EvenMoneyRule, roulette_type::RouletteType, round_event::RoundEvent,
+};
+
+/// Query parameters of a live stream. Every filter is optional, an empty
+/// filter forwards every round of every game.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct LiveFilter {
+    pub game_number: Option<i32>,
+    pub roulette_type: Option<RouletteType>,
+    pub even_money_rule: Option<EvenMoneyRule>,
+    pub agent_name: Option<String>,
+    /// Smallest gap between two forwarded rounds, rounds in between are dropped
+    pub interval_ms: Option<u64>,
+}
+
+impl LiveFilter {
+    /// Narrows `round_event` down to what the client asked for, `None` if
+    /// nothing of it should be sent.
+    pub fn apply(&self, mut round_event: RoundEvent) -> Option<RoundEvent> {
+        if self
+            .game_number
+            .is_some_and(|game_number| game_number != round_event.game_number)
+        {
+            return None;
+        }
+        if self
+            .roulette_type
+            .is_some_and(|roulette_type| roulette_type != round_event.roulette_type)
+        {
+            return None;
+        }
+        if self
+            .even_money_rule
+            .is_some_and(|even_money_rule| even_money_rule != round_event.even_money_rule)
+        {
+            return None;
+        }
+        if let Some(agent_name) = &self.agent_name {
+            round_event
+                .agent_balances
+                .retain(|agent_balance| &agent_balance.agent_name == agent_name);
+            if round_event.agent_balances.is_empty() {
+                return None;
+            }
+        }
+        return Some(round_event);
+    }
+
+    /// Whether enough time went by since `last_sent` to send another round
+    pub fn is_due(&self, last_sent: Option<Instant>, now: Instant) -> bool {
+        return match (self.interval_ms, last_sent) {
+            (Some(interval_ms), Some(last_sent)) => {
+                now.duration_since(last_sent) >= Duration::from_millis(interval_ms)
+            }
+            _ => true,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::broadcast;
+
+    use super::*;
+    use crate::board::board::Board;
+    use crate::json::json_reader::JsonReader;
+    use crate::roulette::{
+        game_runner::run_with_controls, round_event::AgentBalance, run_controls::RunControls,
+    };
+
+    fn create_round_event(game_number: i32) -> RoundEvent {
+        return RoundEvent {
+            game_number,
+            roulette_type: RouletteType::European,
+            even_money_rule: EvenMoneyRule::Standard,
+            round_number: 1,
+            winning_slot: Board::generate(&RouletteType::European).unwrap().slots[0].clone(),
+            agent_balances: vec![
+                AgentBalance {
+                    agent_name: "Agent 1".to_string(),
+                    balance_cents: 1000,
+                    exit_reason: None,
+                },
+                AgentBalance {
+                    agent_name: "Agent 2".to_string(),
+                    balance_cents: 2000,
+                    exit_reason: None,
+                },
+            ],
+        };
+    }
+
+    #[test]
+    fn test_apply_empty_filter() {
+        let round_event = create_round_event(1);
+        assert_eq!(
+            LiveFilter::default().apply(round_event.clone()),
+            Some(round_event)
+        );
+    }
+
+    #[test]
+    fn test_apply_game_number() {
+        let live_filter = LiveFilter {
+            game_number: Some(2),
+            ..LiveFilter::default()
+        };
+        assert!(live_filter.apply(create_round_event(1)).is_none());
+        assert!(live_filter.apply(create_round_event(2)).is_some());
+    }
+
+    #[test]
+    fn test_apply_even_money_rule() {
+        let live_filter = LiveFilter {
+            even_money_rule: Some(EvenMoneyRule::LaPartage),
+            ..LiveFilter::default()
+        };
+        assert!(live_filter.apply(create_round_event(1)).is_none());
+        let round_event = RoundEvent {
+            even_money_rule: EvenMoneyRule::LaPartage,
+            ..create_round_event(1)
+        };
+        assert!(live_filter.apply(round_event).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_apply_two_wheels() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 1;
+        game_config.number_of_rounds = 10;
+        game_config.roulette_types = Some(vec![RouletteType::European, RouletteType::American]);
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let (sender, mut receiver) = broadcast::channel(64);
+        let controls = RunControls {
+            round_events: Some(sender),
+            ..RunControls::default()
+        };
+        run_with_controls(game_config, agents, controls)
+            .await
+            .unwrap();
+        let mut round_events = Vec::new();
+        while let Ok(round_event) = receiver.try_recv() {
+            round_events.push(round_event);
+        }
+
+        // both wheels play a game 1, only the wheel type tells them apart
+        let game_one = LiveFilter {
+            game_number: Some(1),
+            ..LiveFilter::default()
+        };
+        assert_eq!(
+            round_events
+                .iter()
+                .filter_map(|round_event| game_one.apply(round_event.clone()))
+                .count(),
+            round_events.len()
+        );
+        let mut forwarded = 0;
+        for roulette_type in [RouletteType::European, RouletteType::American] {
+            let live_filter = LiveFilter {
+                roulette_type: Some(roulette_type),
+                ..game_one.clone()
+            };
+            let wheel_events: Vec<RoundEvent> = round_events
+                .iter()
+                .filter_map(|round_event| live_filter.apply(round_event.clone()))
+                .collect();
+            assert!(!wheel_events.is_empty());
+            assert!(wheel_events
+                .iter()
+                .all(|round_event| round_event.roulette_type == roulette_type));
+            forwarded += wheel_events.len();
+        }
+        assert_eq!(forwarded, round_events.len());
+    }
+
+    #[test]
+    fn test_apply_agent_name() {
+        let live_filter = LiveFilter {
+            agent_name: Some("Agent 2".to_string()),
+            ..LiveFilter::default()
+        };
+        let round_event = live_filter.apply(create_round_event(1)).unwrap();
+        assert_eq!(round_event.agent_balances.len(), 1);
+        assert_eq!(round_event.agent_balances[0].balance_cents, 2000);
+        let unknown_agent = LiveFilter {
+            agent_name: Some("Agent 3".to_string()),
+            ..LiveFilter::default()
+        };
+        assert!(unknown_agent.apply(create_round_event(1)).is_none());
+    }
+
+    #[test]
+    fn test_is_due() {
+        let live_filter = LiveFilter {
+            interval_ms: Some(100),
+            ..LiveFilter::default()
+        };
+        let now = Instant::now();
+        assert!(live_filter.is_due(None, now));
+        assert!(!live_filter.is_due(Some(now), now + Duration::from_millis(50)));
+        assert!(live_filter.is_due(Some(now), now + Duration::from_millis(100)));
+        assert!(LiveFilter::default().is_due(Some(now), now));
+    }
+}
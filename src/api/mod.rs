@@ -1 +1,4 @@
 pub mod endpoints;
+pub mod live_filter;
+pub mod server;
+pub mod simulation_jobs;
@@ -0,0 +1,30 @@
+use actix_web::{web, App, HttpServer};
+
+use super::{
+    endpoints::{
+        cancel_simulation, cancel_sweep, create_simulation, create_sweep, get_simulation,
+        get_simulation_trajectories, get_sweep, run_with_files, stream_simulation, validate_agents,
+    },
+    simulation_jobs::SimulationJobs,
+};
+
+pub async fn serve(host: &str, port: u16) -> std::io::Result<()> {
+    let simulation_jobs = web::Data::new(SimulationJobs::default());
+    return HttpServer::new(move || {
+        App::new()
+            .app_data(simulation_jobs.clone())
+            .service(run_with_files)
+            .service(create_simulation)
+            .service(get_simulation)
+            .service(get_simulation_trajectories)
+            .service(cancel_simulation)
+            .service(stream_simulation)
+            .service(validate_agents)
+            .service(create_sweep)
+            .service(get_sweep)
+            .service(cancel_sweep)
+    })
+    .bind((host, port))?
+    .run()
+    .await;
+}
@@ -0,0 +1,353 @@
+use core::fmt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{atomic::Ordering, Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::agent::agent::Agent;
+use crate::error::Error;
+use crate::roulette::{
+    game_configs::GameConfig, game_runner::run_with_controls, round_event::RoundEvent,
+    run_controls::RunControls, run_result::RunResult, stats::Stats, sweep_config::SweepConfig,
+    sweep_result::SweepResult, sweep_runner::run_sweep_with_controls,
+};
+
+/// Rounds a live subscriber may fall behind before it starts skipping some
+const ROUND_EVENTS_CAPACITY: usize = 1024;
+/// How long a finished job can still be fetched before it is dropped
+const FINISHED_JOB_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(&self) -> bool {
+        return matches!(
+            self,
+            JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled
+        );
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                JobStatus::Queued => "Queued",
+                JobStatus::Running => "Running",
+                JobStatus::Completed => "Completed",
+                JobStatus::Failed => "Failed",
+                JobStatus::Cancelled => "Cancelled",
+            }
+        );
+    }
+}
+
+/// Which endpoints a job is served under, a sweep is not a simulation and vice versa
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
+pub enum JobKind {
+    Simulation,
+    Sweep,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationJob {
+    pub id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    pub stats: Option<Stats>,
+    /// Throughput of the finished run
+    pub spins_per_second: Option<f64>,
+    /// Rows of a completed sweep, only on sweep jobs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sweep_result: Option<SweepResult>,
+    pub error: Option<String>,
+    #[serde(skip)]
+    controls: RunControls,
+    #[serde(skip)]
+    finished_at: Option<Instant>,
+}
+
+/// What a finished job hands back
+enum JobOutcome {
+    Run(Box<RunResult>),
+    Sweep(SweepResult),
+}
+
+/// Simulations submitted through the API. Jobs are spawned on the server's
+/// runtime, their games are played on the worker pool so long runs don't hold
+/// up the server's workers. Finished jobs are dropped once `ttl` has passed.
+#[derive(Debug, Clone)]
+pub struct SimulationJobs {
+    jobs: Arc<Mutex<HashMap<String, SimulationJob>>>,
+    ttl: Duration,
+}
+
+impl SimulationJobs {
+    pub fn new(ttl: Duration) -> Self {
+        return SimulationJobs {
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        };
+    }
+
+    pub fn submit(&self, game_config: GameConfig, agents: Vec<Agent>) -> SimulationJob {
+        let (round_events, _) = broadcast::channel(ROUND_EVENTS_CAPACITY);
+        let controls = RunControls {
+            round_events: Some(round_events),
+            ..RunControls::default()
+        };
+        let job = self.spawn(JobKind::Simulation, controls, |controls| async move {
+            return run_with_controls(game_config, agents, controls)
+                .await
+                .map(|run_result| JobOutcome::Run(Box::new(run_result)));
+        });
+        info!("Submitted simulation job {}", job.id);
+        return job;
+    }
+
+    /// Runs every point of the sweep in one job, which has no live rounds
+    pub fn submit_sweep(
+        &self,
+        game_config: GameConfig,
+        agents: Vec<Agent>,
+        sweep_config: SweepConfig,
+    ) -> SimulationJob {
+        let job = self.spawn(
+            JobKind::Sweep,
+            RunControls::default(),
+            |controls| async move {
+                return run_sweep_with_controls(game_config, agents, &sweep_config, controls)
+                    .await
+                    .map(JobOutcome::Sweep);
+            },
+        );
+        info!("Submitted sweep job {}", job.id);
+        return job;
+    }
+
+    /// Queues a job and spawns `run` for it, has to be called on the server's runtime
+    fn spawn<F, R>(&self, kind: JobKind, controls: RunControls, run: F) -> SimulationJob
+    where
+        F: FnOnce(RunControls) -> R + 'static,
+        R: Future<Output = Result<JobOutcome, Error>> + 'static,
+    {
+        let job = SimulationJob {
+            id: Uuid::new_v4().to_string(),
+            kind,
+            status: JobStatus::Queued,
+            stats: None,
+            spins_per_second: None,
+            sweep_result: None,
+            error: None,
+            controls,
+            finished_at: None,
+        };
+        {
+            let mut jobs = self.lock();
+            let now = Instant::now();
+            jobs.retain(|_, job| {
+                job.finished_at
+                    .is_none_or(|finished_at| now.duration_since(finished_at) < self.ttl)
+            });
+            jobs.insert(job.id.clone(), job.clone());
+        }
+
+        let jobs = self.clone();
+        let id = job.id.clone();
+        let controls = job.controls.clone();
+        actix_web::rt::spawn(async move {
+            jobs.update(&id, |job| {
+                if job.status == JobStatus::Queued {
+                    job.status = JobStatus::Running;
+                }
+            });
+            // a panicking run must not leave the job running forever
+            let result = actix_web::rt::spawn(run(controls))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(Error::GenericError {
+                        message: "Simulation panicked".to_string(),
+                        nested_error: None,
+                    })
+                });
+            jobs.finish(&id, result);
+        });
+        return job;
+    }
+
+    /// The job with `id`, `None` unless it is of `kind`
+    pub fn get(&self, id: &str, kind: JobKind) -> Option<SimulationJob> {
+        return self.lock().get(id).filter(|job| job.kind == kind).cloned();
+    }
+
+    /// Live rounds of a job that is still going, `None` once it has finished
+    pub fn subscribe(&self, id: &str) -> Option<broadcast::Receiver<RoundEvent>> {
+        let jobs = self.lock();
+        let round_events = jobs.get(id)?.controls.round_events.as_ref()?;
+        return Some(round_events.subscribe());
+    }
+
+    /// Stops a job of `kind` that hasn't finished yet. Finished jobs are returned unchanged.
+    pub fn cancel(&self, id: &str, kind: JobKind) -> Option<SimulationJob> {
+        // the kind of a job never changes, so it can be checked up front
+        self.get(id, kind)?;
+        return self.update(id, |job| {
+            if !job.status.is_finished() {
+                job.controls.cancelled.store(true, Ordering::SeqCst);
+                job.controls.round_events = None;
+                job.status = JobStatus::Cancelled;
+                job.finished_at = Some(Instant::now());
+            }
+        });
+    }
+
+    fn finish(&self, id: &str, result: Result<JobOutcome, Error>) {
+        self.update(id, |job| {
+            // live subscribers get disconnected once the run's sender goes away too
+            job.controls.round_events = None;
+            if job.status == JobStatus::Cancelled {
+                return;
+            }
+            job.finished_at = Some(Instant::now());
+            match &result {
+                Ok(JobOutcome::Run(run_result)) => {
+                    job.stats = Some(run_result.stats.clone());
+                    job.spins_per_second = Some(run_result.get_spins_per_second());
+                    job.status = JobStatus::Completed;
+                }
+                Ok(JobOutcome::Sweep(sweep_result)) => {
+                    job.sweep_result = Some(sweep_result.clone());
+                    job.status = JobStatus::Completed;
+                }
+                Err(err) => {
+                    error!("Simulation job {} failed: {}", job.id, err);
+                    job.error = Some(err.to_string());
+                    job.status = JobStatus::Failed;
+                }
+            }
+        });
+    }
+
+    fn update<F>(&self, id: &str, f: F) -> Option<SimulationJob>
+    where
+        F: FnOnce(&mut SimulationJob),
+    {
+        let mut jobs = self.lock();
+        let job = jobs.get_mut(id)?;
+        f(job);
+        return Some(job.clone());
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, SimulationJob>> {
+        return self.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+    }
+}
+
+impl Default for SimulationJobs {
+    fn default() -> Self {
+        return SimulationJobs::new(FINISHED_JOB_TTL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::json_reader::JsonReader;
+
+    async fn wait_until_finished(
+        simulation_jobs: &SimulationJobs,
+        job: &SimulationJob,
+    ) -> SimulationJob {
+        for _ in 0..600 {
+            let job = simulation_jobs.get(&job.id, job.kind).unwrap();
+            if job.status.is_finished() {
+                return job;
+            }
+            actix_web::rt::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("Simulation job {} did not finish", job.id);
+    }
+
+    #[actix_web::test]
+    async fn test_submit_completes() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 5;
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let simulation_jobs = SimulationJobs::default();
+        let job = simulation_jobs.submit(game_config, agents);
+        let job = wait_until_finished(&simulation_jobs, &job).await;
+        assert_eq!(job.status, JobStatus::Completed);
+        assert!(job.stats.is_some());
+        assert!(job.spins_per_second.is_some());
+        assert!(simulation_jobs.subscribe(&job.id).is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_cancel() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 1000;
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let simulation_jobs = SimulationJobs::default();
+        let job = simulation_jobs.submit(game_config, agents);
+        assert!(simulation_jobs.cancel(&job.id, JobKind::Sweep).is_none());
+        let cancelled_job = simulation_jobs
+            .cancel(&job.id, JobKind::Simulation)
+            .unwrap();
+        assert_eq!(cancelled_job.status, JobStatus::Cancelled);
+        let job = wait_until_finished(&simulation_jobs, &job).await;
+        assert_eq!(job.status, JobStatus::Cancelled);
+        assert!(job.stats.is_none());
+        assert!(simulation_jobs
+            .cancel("unknown", JobKind::Simulation)
+            .is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_submit_sweep_completes() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 5;
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let sweep_config =
+            JsonReader::read_sweep_json("./res/tst/sweep.json", &game_config).unwrap();
+        let simulation_jobs = SimulationJobs::default();
+        let job = simulation_jobs.submit_sweep(game_config, agents.clone(), sweep_config);
+        assert_eq!(job.kind, JobKind::Sweep);
+        assert!(simulation_jobs.subscribe(&job.id).is_none());
+        assert!(simulation_jobs.get(&job.id, JobKind::Simulation).is_none());
+        let job = wait_until_finished(&simulation_jobs, &job).await;
+        assert_eq!(job.status, JobStatus::Completed);
+        assert!(job.stats.is_none());
+        assert_eq!(job.sweep_result.unwrap().rows.len(), 4 * agents.len());
+    }
+
+    #[actix_web::test]
+    async fn test_finished_jobs_expire() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 2;
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let simulation_jobs = SimulationJobs::new(Duration::ZERO);
+        let first_job = simulation_jobs.submit(game_config.clone(), agents.clone());
+        wait_until_finished(&simulation_jobs, &first_job).await;
+        let second_job = simulation_jobs.submit(game_config, agents);
+        assert!(simulation_jobs
+            .get(&first_job.id, JobKind::Simulation)
+            .is_none());
+        assert!(simulation_jobs
+            .get(&second_job.id, JobKind::Simulation)
+            .is_some());
+    }
+}
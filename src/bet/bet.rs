@@ -2,10 +2,21 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    roulette::roulette_type::RouletteType, types::color::Color, types::slot_number::SlotNumber,
+    board::wheel::Wheel,
+    json::validation_report::ValidationIssue,
+    roulette::{
+        game_logs::GameLog, limit_policy::LimitPolicy, roulette_type::RouletteType,
+        table_limits::TableLimits,
+    },
+    strategy::{
+        strategy::{Strategy, StrategyContext},
+        strategy_type::StrategyType,
+    },
+    types::color::Color,
+    types::slot_number::SlotNumber,
 };
 
-use super::{bet_log::BetLog, bet_state::BetState, bet_value::BetValue};
+use super::{bet_log::BetLog, bet_state::BetState, bet_trigger::BetTrigger, bet_value::BetValue};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize, Serialize)]
 pub struct Bet {
@@ -14,62 +25,206 @@ pub struct Bet {
     pub bet_state: BetState,
     pub bet_value: BetValue,
     pub initial_amount_cents: i64,
+    /// Amount the table paid back for this bet in the latest round
+    pub payout_cents: i64,
     pub progression_factor: i64,
+    pub strategy: StrategyType,
+    /// The stake of the current round ran into the table maximum
+    pub table_limit_hit: bool,
+    /// Keeps the bet `Waiting` until the latest spins show a pattern
+    pub trigger: Option<BetTrigger>,
 }
 
 impl Bet {
+    /// Lets the bet's strategy size the next stake once the bet was won or lost.
+    pub fn play_strategy(&mut self, balance_cents: i64, game_logs: &[GameLog]) {
+        if self.bet_state != BetState::Won && !self.bet_state.is_loss() {
+            return;
+        }
+        // the context borrows the bet, so the strategy is taken out meanwhile
+        let mut strategy = std::mem::take(&mut self.strategy);
+        self.amount_cents =
+            strategy.next_amount_cents(&self.strategy_context(balance_cents, game_logs));
+        self.strategy = strategy;
+    }
+
+    /// What the bet's strategy gets to see of the bet, the agent and the game
+    fn strategy_context<'a>(
+        &'a self,
+        balance_cents: i64,
+        game_logs: &'a [GameLog],
+    ) -> StrategyContext<'a> {
+        return StrategyContext {
+            bet_state: self.bet_state,
+            bet_logs: &self.bet_logs,
+            amount_cents: self.amount_cents,
+            initial_amount_cents: self.initial_amount_cents,
+            progression_factor: self.progression_factor,
+            balance_cents,
+            game_logs,
+        };
+    }
+
+    /// Keeps the next stake within the table maximum, following the table's limit policy.
+    pub fn apply_table_limits(&mut self, table_limits: &TableLimits) {
+        self.table_limit_hit = false;
+        if self.bet_state.is_locked() {
+            return;
+        }
+        // a stake spread over several chips has to split evenly
+        let chip_count = self.bet_value.get_chip_count();
+        let maximum_cents = match table_limits.maximum_for(&self.bet_value) {
+            Some(maximum_cents) if self.amount_cents > maximum_cents => {
+                maximum_cents - maximum_cents % chip_count
+            }
+            _ => return,
+        };
+        self.table_limit_hit = true;
+        match table_limits.limit_policy {
+            LimitPolicy::Clamp => self.amount_cents = maximum_cents,
+            LimitPolicy::Reset => {
+                self.strategy.reset();
+                self.amount_cents = self
+                    .strategy
+                    .opening_amount_cents(self.initial_amount_cents)
+                    .min(maximum_cents);
+            }
+            LimitPolicy::Stop => self.bet_state = BetState::Retired,
+        }
+    }
+
     pub fn validate(&mut self, roulette_type: Option<&RouletteType>) {
         let roulette_type = roulette_type.unwrap_or(&RouletteType::European);
-        if self.bet_state != BetState::Active
-            && self.amount_cents <= 0
-            && self.initial_amount_cents <= 0
-            && self.progression_factor <= 0
-        {
+        if !self.find_issues(roulette_type).is_empty() {
             self.bet_state = BetState::Inactive;
-            return;
         }
+    }
 
-        let bet_type_valid: bool = match &self.bet_value {
+    /// Everything that keeps the bet off a `roulette_type` table, with paths
+    /// relative to the bet
+    pub fn find_issues(&self, roulette_type: &RouletteType) -> Vec<ValidationIssue> {
+        let mut issues: Vec<ValidationIssue> = Vec::new();
+        if let Some(message) = Self::find_value_problem(&self.bet_value, roulette_type) {
+            issues.push(ValidationIssue::new("bet_value", &message));
+        }
+        let chip_count = self.bet_value.get_chip_count();
+        if self.initial_amount_cents <= 0 || self.amount_cents <= 0 {
+            issues.push(ValidationIssue::new(
+                "amount_cents",
+                &format!(
+                    "Amount must be positive, got {}",
+                    self.initial_amount_cents.min(self.amount_cents)
+                ),
+            ));
+        } else if let Some(amount_cents) = [self.initial_amount_cents, self.amount_cents]
+            .into_iter()
+            .find(|amount_cents| amount_cents % chip_count != 0)
+        {
+            issues.push(ValidationIssue::new(
+                "amount_cents",
+                &format!(
+                    "Amount must split evenly over the {} chips of the bet, got {}",
+                    chip_count, amount_cents
+                ),
+            ));
+        }
+        if let Some(trigger) = &self.trigger {
+            let problem = Self::find_value_problem(trigger.get_outcome(), roulette_type)
+                .or_else(|| trigger.find_problem());
+            if let Some(message) = problem {
+                issues.push(ValidationIssue::new("trigger", &message));
+            }
+        }
+        if self.progression_factor <= 0 {
+            issues.push(ValidationIssue::new(
+                "progression_factor",
+                &format!(
+                    "Progression factor must be positive, got {}",
+                    self.progression_factor
+                ),
+            ));
+        }
+        return issues;
+    }
+
+    fn find_value_problem(bet_value: &BetValue, roulette_type: &RouletteType) -> Option<String> {
+        let numbers: &[SlotNumber] = match bet_value {
+            BetValue::AdjacentNumbers(adjacent_numbers) => &adjacent_numbers.numbers,
+            BetValue::Number(number) => std::slice::from_ref(number),
+            BetValue::Neighbours { center, .. } => std::slice::from_ref(center),
+            _ => &[],
+        };
+        if numbers.contains(&-1) && *roulette_type != RouletteType::American {
+            return Some(format!("00 is not on the {} wheel", roulette_type));
+        }
+        if let Some(number) = numbers.iter().find(|number| !(-1..=36).contains(*number)) {
+            return Some(format!("{} is not on the wheel", number));
+        }
+
+        return match bet_value {
             BetValue::AdjacentNumbers(adjacent_numbers) => {
-                self.validate_adjacent_numbers(&adjacent_numbers.numbers, roulette_type)
+                if Self::validate_adjacent_numbers(&adjacent_numbers.numbers, roulette_type) {
+                    None
+                } else {
+                    Some(format!(
+                        "[{}] is not a split, trio, corner, first four or top line on the {} table",
+                        adjacent_numbers
+                            .numbers
+                            .iter()
+                            .map(|number| match number {
+                                -1 => "00".to_string(),
+                                n => n.to_string(),
+                            })
+                            .join(", "),
+                        roulette_type
+                    ))
+                }
+            }
+            BetValue::Color(color) => {
+                if *color == Color::Red || *color == Color::Black {
+                    None
+                } else {
+                    Some(format!("{} can't be bet on, only Red or Black", color))
+                }
             }
-            BetValue::Color(color) => *color == Color::Red || *color == Color::Black,
-            BetValue::Column(_) => true,
-            BetValue::Dozen(_) => true,
-            BetValue::EvenOdd(_) => true,
-            BetValue::Half(_) => true,
-            BetValue::Number(number) => {
-                let n = *number;
-                let res;
-                if n == -1 && roulette_type == &RouletteType::American {
-                    res = true
+            BetValue::CallBet(call_bet) => {
+                if call_bet.follows_wheel(&Wheel::new(roulette_type)) {
+                    None
                 } else {
-                    res = n >= 0 && n <= 36;
+                    Some(format!(
+                        "{} is not played on the {} wheel",
+                        call_bet, roulette_type
+                    ))
                 }
-                res
             }
-            BetValue::Row(_) => true,
+            BetValue::Column(_) => None,
+            BetValue::Dozen(_) => None,
+            BetValue::EvenOdd(_) => None,
+            BetValue::Half(_) => None,
+            BetValue::Neighbours { center, radius } => Wheel::new(roulette_type)
+                .neighbours(*center, *radius)
+                .err()
+                .map(|e| e.get_message()),
+            BetValue::Number(_) => None,
+            BetValue::Row(_) => None,
             BetValue::DoubleColumn(double_column) => {
-                (double_column.columns[0].value() - double_column.columns[1].value()).abs() == 1
+                if (double_column.columns[0].value() - double_column.columns[1].value()).abs() == 1
+                {
+                    None
+                } else {
+                    Some(format!(
+                        "Columns {} and {} are not next to each other",
+                        double_column.columns[0], double_column.columns[1]
+                    ))
+                }
             }
         };
-
-        if !bet_type_valid {
-            self.bet_state = BetState::Inactive;
-        }
     }
 
-    fn validate_adjacent_numbers(
-        &self,
-        numbers: &Vec<SlotNumber>,
-        roulette_type: &RouletteType,
-    ) -> bool {
-        let num_count = numbers.len();
-        let unique_numbers = numbers.iter().unique().count();
-        if unique_numbers != num_count {
-            return false;
-        }
-        if num_count > 4 || num_count < 2 {
+    /// Accepts the numbers in any order, 00 sorting first as -1
+    fn validate_adjacent_numbers(numbers: &[SlotNumber], roulette_type: &RouletteType) -> bool {
+        let numbers: Vec<SlotNumber> = numbers.iter().copied().sorted().collect();
+        if numbers.iter().unique().count() != numbers.len() {
             return false;
         }
         for number in numbers.iter() {
@@ -80,60 +235,54 @@ impl Bet {
                 return false;
             }
         }
+        let is_american = *roulette_type == RouletteType::American;
+        let smallest = numbers[0];
 
-        match num_count {
-            2 => {
-                let smaller;
-                let larger;
-                if numbers[0] < numbers[1] {
-                    smaller = numbers[0];
-                    larger = numbers[1];
+        return match numbers.len() {
+            2 if smallest <= 0 => {
+                let zero_splits: &[[SlotNumber; 2]] = if is_american {
+                    &[[-1, 0], [-1, 2], [-1, 3], [0, 1], [0, 2]]
                 } else {
-                    smaller = numbers[1];
-                    larger = numbers[0];
-                }
-                if smaller == 0 || smaller == -1 {
-                    if *roulette_type == RouletteType::American {
-                        return *numbers == vec![-1, 3] || *numbers == vec![0, 1];
-                    } else if *roulette_type == RouletteType::European {
-                        return larger == 1 || larger == 2 || larger == 3;
-                    }
-                }
-                if larger - smaller == 3 {
-                    // horizontal case
-                    return true;
-                } else if larger - smaller == 1 {
-                    // vertical case
-                    return (larger % 3 - smaller % 3).abs() == 1;
-                }
+                    &[[0, 1], [0, 2], [0, 3]]
+                };
+                zero_splits.iter().any(|split| *split == *numbers)
+            }
+            2 => {
+                let (smaller, larger) = (numbers[0], numbers[1]);
+                // vertical case, the next row of the same column
+                larger - smaller == 3
+                    // horizontal case, within a row of three
+                    || (larger - smaller == 1 && smaller % 3 != 0)
             }
             3 => {
-                if *roulette_type == RouletteType::American {
-                    return *numbers == vec![-1, 2, 3] || *numbers == vec![0, 1, 2];
-                } else if *roulette_type == RouletteType::European {
-                    return *numbers == vec![0, 2, 3] || *numbers == vec![0, 1, 2];
-                }
+                let trios: &[[SlotNumber; 3]] = if is_american {
+                    &[[-1, 0, 2], [-1, 2, 3], [0, 1, 2]]
+                } else {
+                    &[[0, 1, 2], [0, 2, 3]]
+                };
+                trios.iter().any(|trio| *trio == *numbers)
             }
+            // first four
+            4 if smallest == 0 => !is_american && numbers == [0, 1, 2, 3],
             4 => {
-                let min_num = *numbers.iter().min().unwrap();
-                if min_num % 3 == 0 {
-                    return false;
-                }
-                if *numbers == vec![min_num, min_num + 1, min_num + 3, min_num + 4] {
-                    return true;
-                }
+                smallest % 3 != 0 && numbers == [smallest, smallest + 1, smallest + 3, smallest + 4]
             }
-            _ => return false,
-        }
-        return false;
+            // five-number top line
+            5 => is_american && numbers == [-1, 0, 1, 2, 3],
+            _ => false,
+        };
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
+
     use super::*;
+    use crate::board::board::Board;
     use crate::types::{
-        adjacent_numbers::AdjacentNumbers, column::Column, double_column::DoubleColumn,
+        adjacent_numbers::AdjacentNumbers, call_bet::CallBet, column::Column,
+        double_column::DoubleColumn,
     };
 
     fn create_test_bet(bet_value: BetValue) -> Bet {
@@ -143,7 +292,11 @@ mod test {
             bet_state: BetState::Active,
             bet_value,
             initial_amount_cents: 1000,
+            payout_cents: 0,
             progression_factor: 2,
+            strategy: StrategyType::default(),
+            trigger: None,
+            table_limit_hit: false,
         }
     }
 
@@ -201,6 +354,64 @@ mod test {
         assert_eq!(bet.bet_state, BetState::Inactive);
     }
 
+    // CallBet
+    #[test]
+    fn test_validate_call_bet_european() {
+        let mut bet = create_test_bet(BetValue::CallBet(CallBet::VoisinsDuZero));
+        bet.initial_amount_cents = 900;
+        bet.amount_cents = 900;
+        bet.validate(Some(&RouletteType::European));
+        assert_eq!(bet.bet_state, BetState::Active);
+    }
+
+    #[test]
+    fn test_validate_call_bet_uneven_amount() {
+        // Voisins du Zero takes 9 chips
+        let bet = create_test_bet(BetValue::CallBet(CallBet::VoisinsDuZero));
+        assert_eq!(
+            bet.find_issues(&RouletteType::European)[0].to_string(),
+            "amount_cents: Amount must split evenly over the 9 chips of the bet, got 1000"
+        );
+        let bet = create_test_bet(BetValue::Neighbours {
+            center: 17,
+            radius: 1,
+        });
+        assert_eq!(bet.find_issues(&RouletteType::European).len(), 1);
+    }
+
+    #[test]
+    fn test_validate_call_bet_american() {
+        let bet = create_test_bet(BetValue::CallBet(CallBet::Orphelins));
+        assert_eq!(
+            bet.find_issues(&RouletteType::American)[0].to_string(),
+            "bet_value: Orphelins is not played on the American wheel"
+        );
+    }
+
+    // Neighbours
+    #[test]
+    fn test_validate_neighbours() {
+        let mut bet = create_test_bet(BetValue::Neighbours {
+            center: -1,
+            radius: 2,
+        });
+        bet.validate(Some(&RouletteType::American));
+        assert_eq!(bet.bet_state, BetState::Active);
+        assert_eq!(
+            bet.find_issues(&RouletteType::European)[0].to_string(),
+            "bet_value: 00 is not on the European wheel"
+        );
+
+        let bet = create_test_bet(BetValue::Neighbours {
+            center: 17,
+            radius: 19,
+        });
+        assert_eq!(
+            bet.find_issues(&RouletteType::European)[0].to_string(),
+            "bet_value: Radius 19 covers more than the 37 pockets on the wheel"
+        );
+    }
+
     #[test]
     fn test_validate_adjacent_numbers_invalid_duo() {
         let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
@@ -210,6 +421,26 @@ mod test {
         assert_eq!(bet.bet_state, BetState::Inactive);
     }
 
+    #[test]
+    fn test_validate_adjacent_numbers_splits() {
+        for (numbers, bet_state) in [
+            (vec![1, 2], BetState::Active),
+            (vec![2, 3], BetState::Active),
+            (vec![3, 4], BetState::Inactive),
+            (vec![1, 4], BetState::Active),
+            (vec![35, 36], BetState::Active),
+            (vec![33, 36], BetState::Active),
+            (vec![36, 37], BetState::Inactive),
+            (vec![6, 7], BetState::Inactive),
+        ] {
+            let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
+                numbers: numbers.clone(),
+            }));
+            bet.validate(Some(&RouletteType::European));
+            assert_eq!(bet.bet_state, bet_state, "{:?}", numbers);
+        }
+    }
+
     #[test]
     fn test_validate_adjacent_numbers_invalid_triple() {
         let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
@@ -256,6 +487,52 @@ mod test {
         assert_eq!(european_bet.bet_state, BetState::Active);
     }
 
+    #[test]
+    fn test_validate_adjacent_numbers_any_order() {
+        for numbers in [
+            vec![3, 0],
+            vec![2, 0, 1],
+            vec![5, 1, 4, 2],
+            vec![3, 1, 2, 0],
+        ] {
+            let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
+                numbers: numbers.clone(),
+            }));
+            bet.validate(Some(&RouletteType::European));
+            assert_eq!(bet.bet_state, BetState::Active, "{:?}", numbers);
+        }
+    }
+
+    #[test]
+    fn test_validate_adjacent_numbers_american_top_line() {
+        let valid_numbers = [
+            vec![0, -1],
+            vec![2, -1],
+            vec![0, 2],
+            vec![2, 0, -1],
+            vec![-1, 0, 1, 2, 3],
+        ];
+        for numbers in valid_numbers {
+            let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
+                numbers: numbers.clone(),
+            }));
+            bet.validate(Some(&RouletteType::American));
+            assert_eq!(bet.bet_state, BetState::Active, "{:?}", numbers);
+        }
+
+        let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
+            numbers: vec![0, 1, 2, 3],
+        }));
+        bet.validate(Some(&RouletteType::American));
+        assert_eq!(bet.bet_state, BetState::Inactive);
+
+        let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
+            numbers: vec![0, 3],
+        }));
+        bet.validate(Some(&RouletteType::American));
+        assert_eq!(bet.bet_state, BetState::Inactive);
+    }
+
     #[test]
     fn test_validate_adjacent_numbers_repeat_numbers() {
         let mut bet = create_test_bet(BetValue::AdjacentNumbers(AdjacentNumbers {
@@ -264,4 +541,113 @@ mod test {
         bet.validate(Some(&RouletteType::American));
         assert_eq!(bet.bet_state, BetState::Inactive);
     }
+
+    #[test]
+    fn test_find_issues() {
+        let mut bet = create_test_bet(BetValue::Number(-1));
+        bet.amount_cents = 0;
+        bet.progression_factor = -1;
+        let fields: Vec<String> = bet
+            .find_issues(&RouletteType::European)
+            .into_iter()
+            .map(|issue| issue.path)
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["bet_value", "amount_cents", "progression_factor"]
+        );
+        bet.validate(Some(&RouletteType::American));
+        assert_eq!(bet.bet_state, BetState::Inactive);
+    }
+
+    #[test]
+    fn test_apply_table_limits() {
+        let mut table_limits = TableLimits {
+            inside_maximum_cents: Some(5000),
+            ..TableLimits::default()
+        };
+        let mut bet = create_test_bet(BetValue::Number(17));
+        bet.amount_cents = 8000;
+        bet.apply_table_limits(&table_limits);
+        assert!(bet.table_limit_hit);
+        assert_eq!(bet.amount_cents, 5000);
+        bet.apply_table_limits(&table_limits);
+        assert!(!bet.table_limit_hit);
+
+        table_limits.limit_policy = LimitPolicy::Reset;
+        bet.amount_cents = 8000;
+        bet.apply_table_limits(&table_limits);
+        assert_eq!(bet.amount_cents, bet.initial_amount_cents);
+
+        table_limits.limit_policy = LimitPolicy::Stop;
+        bet.amount_cents = 8000;
+        bet.apply_table_limits(&table_limits);
+        assert!(bet.table_limit_hit);
+        assert_eq!(bet.bet_state, BetState::Retired);
+    }
+
+    #[test]
+    fn test_apply_table_limits_call_bet() {
+        let table_limits = TableLimits {
+            bet_type_maximums_cents: BTreeMap::from([("CallBet".to_string(), 4000)]),
+            ..TableLimits::default()
+        };
+        // Jeu Zero takes 4 chips, Tiers du Cylindre 6
+        let mut bet = create_test_bet(BetValue::CallBet(CallBet::JeuZero));
+        bet.amount_cents = 8000;
+        bet.apply_table_limits(&table_limits);
+        assert_eq!(bet.amount_cents, 4000);
+        let mut bet = create_test_bet(BetValue::CallBet(CallBet::TiersDuCylindre));
+        bet.amount_cents = 8100;
+        bet.apply_table_limits(&table_limits);
+        assert_eq!(bet.amount_cents, 3996);
+    }
+
+    #[test]
+    fn test_apply_table_limits_outside_bet() {
+        let table_limits = TableLimits {
+            inside_maximum_cents: Some(500),
+            ..TableLimits::default()
+        };
+        let mut bet = create_test_bet(BetValue::Color(Color::Red));
+        bet.apply_table_limits(&table_limits);
+        assert!(!bet.table_limit_hit);
+        assert_eq!(bet.amount_cents, 1000);
+    }
+
+    #[test]
+    fn test_strategy_context() {
+        let mut bet = create_test_bet(BetValue::Color(Color::Red));
+        bet.bet_state = BetState::Lost;
+        bet.bet_logs = vec![BetLog {
+            round_number: 2,
+            amount_cents: 1000,
+            bet_state: BetState::Lost,
+            payout_cents: 0,
+            table_limit_hit: false,
+        }];
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let game_logs: Vec<GameLog> = board.slots[..2]
+            .iter()
+            .enumerate()
+            .map(|(index, slot)| GameLog {
+                round_number: index as i32 + 1,
+                winning_slot: slot.clone(),
+            })
+            .collect();
+        assert_eq!(
+            bet.strategy_context(98000, &game_logs),
+            StrategyContext {
+                bet_state: BetState::Lost,
+                bet_logs: &bet.bet_logs,
+                amount_cents: 1000,
+                initial_amount_cents: 1000,
+                progression_factor: 2,
+                balance_cents: 98000,
+                game_logs: &game_logs,
+            }
+        );
+        bet.play_strategy(98000, &game_logs);
+        assert_eq!(bet.amount_cents, 2000);
+    }
 }
@@ -7,4 +7,56 @@ pub struct BetLog {
     pub round_number: i32,
     pub amount_cents: i64,
     pub bet_state: BetState,
+    pub payout_cents: i64,
+    /// The stake of this round ran into the table maximum
+    pub table_limit_hit: bool,
+}
+
+impl BetLog {
+    /// Stake taken from the agent this round. Imprisoned bets are settled on
+    /// the stake collected when they were first placed, retired bets aren't placed.
+    pub fn staked_cents(&self) -> i64 {
+        return match self.bet_state {
+            BetState::Released | BetState::Forfeited | BetState::Retired => 0,
+            _ => self.amount_cents,
+        };
+    }
+
+    pub fn income_cents(&self) -> i64 {
+        return self.payout_cents - self.staked_cents();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_bet_log(bet_state: BetState, payout_cents: i64) -> BetLog {
+        BetLog {
+            round_number: 1,
+            amount_cents: 1000,
+            bet_state,
+            payout_cents,
+            table_limit_hit: false,
+        }
+    }
+
+    #[test]
+    fn test_income_cents() {
+        assert_eq!(create_bet_log(BetState::Won, 2000).income_cents(), 1000);
+        assert_eq!(create_bet_log(BetState::Lost, 0).income_cents(), -1000);
+        assert_eq!(
+            create_bet_log(BetState::Surrendered, 500).income_cents(),
+            -500
+        );
+    }
+
+    #[test]
+    fn test_income_cents_en_prison() {
+        let imprisoned = create_bet_log(BetState::Imprisoned, 0);
+        let released = create_bet_log(BetState::Released, 1000);
+        let forfeited = create_bet_log(BetState::Forfeited, 0);
+        assert_eq!(imprisoned.income_cents() + released.income_cents(), 0);
+        assert_eq!(imprisoned.income_cents() + forfeited.income_cents(), -1000);
+    }
 }
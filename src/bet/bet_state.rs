@@ -9,6 +9,35 @@ pub enum BetState {
     Lost,
     Active,
     Inactive,
+    /// Even-money bet held on the table by En Prison after a zero
+    Imprisoned,
+    /// Imprisoned bet that won its next spin and was handed back
+    Released,
+    /// Imprisoned bet that lost its next spin
+    Forfeited,
+    /// Even-money bet that got half its stake back after a zero
+    Surrendered,
+    /// Bet taken off the table for the rest of the game
+    Retired,
+    /// Bet whose trigger didn't fire this round
+    Waiting,
+}
+
+impl BetState {
+    pub fn is_loss(&self) -> bool {
+        return matches!(
+            self,
+            BetState::Lost | BetState::Forfeited | BetState::Surrendered
+        );
+    }
+
+    /// States the agent can't change between rounds
+    pub fn is_locked(&self) -> bool {
+        return matches!(
+            self,
+            BetState::Imprisoned | BetState::Retired | BetState::Waiting
+        );
+    }
 }
 
 impl FromStr for BetState {
@@ -20,6 +49,12 @@ impl FromStr for BetState {
             "Lost" => Ok(BetState::Lost),
             "Active" => Ok(BetState::Active),
             "Inactive" => Ok(BetState::Inactive),
+            "Imprisoned" => Ok(BetState::Imprisoned),
+            "Released" => Ok(BetState::Released),
+            "Forfeited" => Ok(BetState::Forfeited),
+            "Surrendered" => Ok(BetState::Surrendered),
+            "Retired" => Ok(BetState::Retired),
+            "Waiting" => Ok(BetState::Waiting),
             _ => Err(format!("{} is not a valid bet type", s)),
         }
     }
@@ -35,6 +70,12 @@ impl fmt::Display for BetState {
                 BetState::Lost => "Lost",
                 BetState::Inactive => "Inactive",
                 BetState::Active => "Active",
+                BetState::Imprisoned => "Imprisoned",
+                BetState::Released => "Released",
+                BetState::Forfeited => "Forfeited",
+                BetState::Surrendered => "Surrendered",
+                BetState::Retired => "Retired",
+                BetState::Waiting => "Waiting",
             }
         );
     }
@@ -0,0 +1,283 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{board::wheel::Wheel, error::Error, roulette::game_logs::GameLog};
+
+use super::bet_value::BetValue;
+
+/// Pattern in the latest spins a bet waits for before it is placed. `of`
+/// is the outcome watched, any bet value that would have won counts as a hit.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum BetTrigger {
+    /// The last `length` spins were all hits
+    Streak { of: BetValue, length: usize },
+    /// None of the last `spins` spins was a hit
+    Sleeper { of: BetValue, spins: usize },
+    /// At least `min_hits` of the last `window` spins were hits
+    Hot {
+        of: BetValue,
+        window: usize,
+        min_hits: usize,
+    },
+    /// At most `max_hits` of the last `window` spins were hits
+    Cold {
+        of: BetValue,
+        window: usize,
+        max_hits: usize,
+    },
+}
+
+impl BetTrigger {
+    const NAME: &'static str = "BetTrigger";
+
+    /// Whether the spins of `game_logs` show the pattern. Never met before
+    /// enough spins were played to tell.
+    pub fn is_met(&self, game_logs: &[GameLog], wheel: &Wheel) -> bool {
+        let spins = self.get_spins_watched();
+        if game_logs.len() < spins {
+            return false;
+        }
+        let hits = game_logs[game_logs.len() - spins..]
+            .iter()
+            .filter(|game_log| self.get_outcome().covers(&game_log.winning_slot, wheel))
+            .count();
+        return match self {
+            BetTrigger::Streak { .. } => hits == spins,
+            BetTrigger::Sleeper { .. } => hits == 0,
+            BetTrigger::Hot { min_hits, .. } => hits >= *min_hits,
+            BetTrigger::Cold { max_hits, .. } => hits <= *max_hits,
+        };
+    }
+
+    pub fn get_outcome(&self) -> &BetValue {
+        return match self {
+            BetTrigger::Streak { of, .. }
+            | BetTrigger::Sleeper { of, .. }
+            | BetTrigger::Hot { of, .. }
+            | BetTrigger::Cold { of, .. } => of,
+        };
+    }
+
+    /// Latest spins the pattern is looked for in
+    fn get_spins_watched(&self) -> usize {
+        return match self {
+            BetTrigger::Streak { length, .. } => *length,
+            BetTrigger::Sleeper { spins, .. } => *spins,
+            BetTrigger::Hot { window, .. } | BetTrigger::Cold { window, .. } => *window,
+        };
+    }
+
+    /// What keeps the trigger from ever firing, apart from its outcome
+    pub fn find_problem(&self) -> Option<String> {
+        let spins = self.get_spins_watched();
+        if spins == 0 {
+            return Some(String::from("Trigger must watch at least one spin"));
+        }
+        if let BetTrigger::Hot { min_hits, .. } = self {
+            if *min_hits > spins {
+                return Some(format!(
+                    "{} hits can't happen in a window of {} spins",
+                    min_hits, spins
+                ));
+            }
+        }
+        return None;
+    }
+
+    fn usize_from(parameters: &Value, key: &str) -> Result<usize, Error> {
+        return parameters[key]
+            .as_u64()
+            .map(|n| n as usize)
+            .ok_or(Error::DeserializatonError {
+                message: format!("{} of {} is not a positive number", key, Self::NAME),
+                de_str: None,
+                value: Some(parameters.clone()),
+                nested_error: None,
+            });
+    }
+}
+
+impl fmt::Display for BetTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let of = self.get_outcome();
+        let outcome = format!("{} {}", of.get_type(), of.get_value_string());
+        return match self {
+            BetTrigger::Streak { length, .. } => {
+                write!(f, "{} {} in a row", outcome, length)
+            }
+            BetTrigger::Sleeper { spins, .. } => {
+                write!(f, "{} missing for {} spins", outcome, spins)
+            }
+            BetTrigger::Hot {
+                window, min_hits, ..
+            } => write!(f, "{} hit {}+ times in {} spins", outcome, min_hits, window),
+            BetTrigger::Cold {
+                window, max_hits, ..
+            } => write!(
+                f,
+                "{} hit at most {} times in {} spins",
+                outcome, max_hits, window
+            ),
+        };
+    }
+}
+
+impl TryFrom<Value> for BetTrigger {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let val_obj = value.as_object().ok_or(Error::DeserializatonError {
+            message: format!(
+                "Value passed onto {}::try_from is not an object",
+                Self::NAME
+            ),
+            de_str: None,
+            value: Some(value.clone()),
+            nested_error: None,
+        })?;
+        let (name, parameters) = val_obj.iter().next().ok_or(Error::DeserializatonError {
+            message: format!(
+                "Value passed onto {}::try_from is an empty object",
+                Self::NAME
+            ),
+            de_str: None,
+            value: Some(value.clone()),
+            nested_error: None,
+        })?;
+        let of = BetValue::try_from(parameters["of"].clone())?;
+        return match name.as_str() {
+            "Streak" => Ok(BetTrigger::Streak {
+                of,
+                length: Self::usize_from(parameters, "length")?,
+            }),
+            "Sleeper" => Ok(BetTrigger::Sleeper {
+                of,
+                spins: Self::usize_from(parameters, "spins")?,
+            }),
+            "Hot" => Ok(BetTrigger::Hot {
+                of,
+                window: Self::usize_from(parameters, "window")?,
+                min_hits: Self::usize_from(parameters, "min_hits")?,
+            }),
+            "Cold" => Ok(BetTrigger::Cold {
+                of,
+                window: Self::usize_from(parameters, "window")?,
+                max_hits: Self::usize_from(parameters, "max_hits")?,
+            }),
+            _ => Err(Error::DeserializatonError {
+                message: format!("Invalid trigger: {}", name),
+                de_str: None,
+                value: Some(value.clone()),
+                nested_error: None,
+            }),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::board::Board;
+    use crate::roulette::roulette_type::RouletteType;
+    use crate::types::{color::Color, dozen::Dozen};
+    use serde_json::json;
+
+    fn create_game_logs(numbers: &[i8]) -> (Vec<GameLog>, Wheel) {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let game_logs = numbers
+            .iter()
+            .enumerate()
+            .map(|(index, number)| GameLog {
+                round_number: index as i32 + 1,
+                winning_slot: board
+                    .slots
+                    .iter()
+                    .find(|slot| slot.number == *number)
+                    .unwrap()
+                    .clone(),
+            })
+            .collect();
+        return (game_logs, board.wheel);
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(
+            BetTrigger::try_from(json!({"Streak": {"of": {"Color": "Red"}, "length": 5}})).unwrap(),
+            BetTrigger::Streak {
+                of: BetValue::Color(Color::Red),
+                length: 5
+            }
+        );
+        assert_eq!(
+            BetTrigger::try_from(
+                json!({"Cold": {"of": {"Number": "17"}, "window": 100, "max_hits": 0}})
+            )
+            .unwrap(),
+            BetTrigger::Cold {
+                of: BetValue::Number(17),
+                window: 100,
+                max_hits: 0
+            }
+        );
+        assert!(BetTrigger::try_from(json!({"Streak": {"of": {"Color": "Red"}}})).is_err());
+        assert!(BetTrigger::try_from(json!({"Often": {"of": {"Color": "Red"}}})).is_err());
+    }
+
+    #[test]
+    fn test_streak() {
+        let trigger = BetTrigger::Streak {
+            of: BetValue::Color(Color::Red),
+            length: 3,
+        };
+        let (game_logs, wheel) = create_game_logs(&[2, 1, 3, 5]);
+        assert!(trigger.is_met(&game_logs, &wheel));
+        assert!(!trigger.is_met(&game_logs[..3], &wheel));
+        assert!(!trigger.is_met(&game_logs[..2], &wheel));
+    }
+
+    #[test]
+    fn test_sleeper() {
+        let trigger = BetTrigger::Sleeper {
+            of: BetValue::Dozen(Dozen::Three),
+            spins: 3,
+        };
+        let (game_logs, wheel) = create_game_logs(&[30, 1, 13, 0]);
+        assert!(trigger.is_met(&game_logs, &wheel));
+        assert!(!trigger.is_met(&game_logs[..3], &wheel));
+    }
+
+    #[test]
+    fn test_hot_and_cold() {
+        let (game_logs, wheel) = create_game_logs(&[17, 4, 17, 9, 17]);
+        let hot = BetTrigger::Hot {
+            of: BetValue::Number(17),
+            window: 4,
+            min_hits: 2,
+        };
+        assert!(hot.is_met(&game_logs, &wheel));
+        let cold = BetTrigger::Cold {
+            of: BetValue::Number(4),
+            window: 3,
+            max_hits: 0,
+        };
+        assert!(cold.is_met(&game_logs, &wheel));
+        assert!(!cold.is_met(&game_logs[..4], &wheel));
+    }
+
+    #[test]
+    fn test_find_problem() {
+        let trigger = BetTrigger::Hot {
+            of: BetValue::Number(17),
+            window: 4,
+            min_hits: 5,
+        };
+        assert!(trigger.find_problem().is_some());
+        let trigger = BetTrigger::Sleeper {
+            of: BetValue::Number(17),
+            spins: 0,
+        };
+        assert!(trigger.find_problem().is_some());
+    }
+}
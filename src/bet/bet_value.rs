@@ -1,23 +1,34 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::naming_scheme::NamingScheme;
 use crate::{
+    board::{slot::Slot, wheel::Wheel},
     error::Error,
+    roulette::roulette_type::RouletteType,
     types::{
-        adjacent_numbers::AdjacentNumbers, color::Color, column::Column,
+        adjacent_numbers::AdjacentNumbers, call_bet::CallBet, color::Color, column::Column,
         double_column::DoubleColumn, dozen::Dozen, even_odd::EvenOdd, half::Half, row::Row,
+        slot_number::SlotNumber,
     },
 };
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum BetValue {
     AdjacentNumbers(AdjacentNumbers),
+    CallBet(CallBet),
     Color(Color),
     Column(Column),
     DoubleColumn(DoubleColumn),
     Dozen(Dozen),
     EvenOdd(EvenOdd),
     Half(Half),
+    /// `center` and the `radius` pockets on each side of it on the wheel, a
+    /// straight-up on each
+    Neighbours {
+        center: SlotNumber,
+        radius: usize,
+    },
     Number(i8),
     Row(Row),
 }
@@ -27,28 +38,100 @@ impl BetValue {
     pub fn get_type(&self) -> String {
         return String::from(match self {
             BetValue::AdjacentNumbers(_) => "Adjacent Numbers",
+            BetValue::CallBet(_) => "Call Bet",
             BetValue::Color(_) => "Color",
             BetValue::Column(_) => "Column",
             BetValue::DoubleColumn(_) => "DoubleColumn",
             BetValue::Dozen(_) => "Dozen",
             BetValue::EvenOdd(_) => "EvenOdd",
             BetValue::Half(_) => "Half",
+            BetValue::Neighbours { .. } => "Neighbours",
             BetValue::Number(_) => "Number",
             BetValue::Row(_) => "Row",
         });
     }
+    /// Name of the variant as written in agents.json
+    pub fn get_variant_name(&self) -> &'static str {
+        return match self {
+            BetValue::AdjacentNumbers(_) => "AdjacentNumbers",
+            BetValue::CallBet(_) => "CallBet",
+            BetValue::Color(_) => "Color",
+            BetValue::Column(_) => "Column",
+            BetValue::DoubleColumn(_) => "DoubleColumn",
+            BetValue::Dozen(_) => "Dozen",
+            BetValue::EvenOdd(_) => "EvenOdd",
+            BetValue::Half(_) => "Half",
+            BetValue::Neighbours { .. } => "Neighbours",
+            BetValue::Number(_) => "Number",
+            BetValue::Row(_) => "Row",
+        };
+    }
+
+    /// Bets placed on the numbers of the layout, call bets and neighbours
+    /// being a set of such bets
+    pub fn is_inside(&self) -> bool {
+        return matches!(
+            self,
+            BetValue::AdjacentNumbers(_)
+                | BetValue::CallBet(_)
+                | BetValue::Neighbours { .. }
+                | BetValue::Number(_)
+        );
+    }
+
+    /// Chips it takes to place the bet once, its stake is split evenly over them
+    pub fn get_chip_count(&self) -> i64 {
+        return match self {
+            BetValue::CallBet(call_bet) => call_bet.get_chip_count(),
+            BetValue::Neighbours { radius, .. } => *radius as i64 * 2 + 1,
+            _ => 1,
+        };
+    }
+
+    /// Whether the bet wins when the ball lands on `slot` of `wheel`
+    pub fn covers(&self, slot: &Slot, wheel: &Wheel) -> bool {
+        return match self {
+            BetValue::AdjacentNumbers(adjacent_numbers) => {
+                adjacent_numbers.numbers.contains(&slot.number)
+            }
+            BetValue::CallBet(call_bet) => call_bet
+                .get_placements()
+                .iter()
+                .any(|placement| placement.numbers.contains(&slot.number)),
+            BetValue::Color(color) => *color == slot.color,
+            BetValue::Column(column) => *column == slot.column,
+            BetValue::DoubleColumn(double_column) => double_column.columns.contains(&slot.column),
+            BetValue::Dozen(dozen) => *dozen == slot.dozen,
+            BetValue::EvenOdd(even_odd) => *even_odd == slot.even_odd,
+            BetValue::Half(half) => *half == slot.half,
+            BetValue::Neighbours { center, radius } => wheel
+                .distance(*center, slot.number)
+                .is_some_and(|distance| distance <= *radius),
+            BetValue::Number(number) => *number == slot.number,
+            BetValue::Row(row) => *row == slot.row,
+        };
+    }
+
+    pub fn is_even_money(&self) -> bool {
+        return matches!(
+            self,
+            BetValue::Color(_) | BetValue::EvenOdd(_) | BetValue::Half(_)
+        );
+    }
+
     pub fn get_value_string(&self) -> String {
         return match self {
             BetValue::AdjacentNumbers(adjacent_numbers) => {
                 let mut s: String = String::new();
                 let numbers_len = adjacent_numbers.numbers.len();
-                s.push_str("[");
+                s.push('[');
                 for i in 0..numbers_len - 1 {
                     s.push_str(format!("{} ", adjacent_numbers.numbers[i]).as_str());
                 }
                 s.push_str(format!("{}]", adjacent_numbers.numbers[numbers_len - 1]).as_str());
                 return s;
             }
+            BetValue::CallBet(call_bet) => call_bet.to_string(),
             BetValue::Color(color) => color.to_string(),
             BetValue::Column(column) => column.to_string(),
             BetValue::DoubleColumn(double_column) => {
@@ -61,10 +144,59 @@ impl BetValue {
             BetValue::Dozen(dozen) => dozen.to_string(),
             BetValue::EvenOdd(even_odd) => even_odd.to_string(),
             BetValue::Half(half) => half.to_string(),
+            BetValue::Neighbours { center, radius } => {
+                let center = match center {
+                    -1 => "00".to_string(),
+                    n => n.to_string(),
+                };
+                return format!("{} and {} neighbours", center, radius);
+            }
             BetValue::Number(number) => number.to_string(),
             BetValue::Row(row) => row.to_string(),
         };
     }
+
+    fn try_neighbours_from(value: &Value) -> Result<Self, Error> {
+        let center = value["center"].as_str().ok_or(Error::DeserializatonError {
+            message: "Neighbours center is not a valid string".to_string(),
+            de_str: None,
+            value: Some(value.clone()),
+            nested_error: None,
+        })?;
+        let radius = value["radius"].as_u64().ok_or(Error::DeserializatonError {
+            message: "Neighbours radius is not a positive number".to_string(),
+            de_str: None,
+            value: Some(value.clone()),
+            nested_error: None,
+        })?;
+        // Bound by the larger wheel here; the table's own wheel is checked
+        // when the bet is validated.
+        let max_radius = Wheel::new(&RouletteType::American).pockets.len() / 2;
+        if radius > max_radius as u64 {
+            return Err(Error::DeserializatonError {
+                message: format!(
+                    "Neighbours radius {} is larger than half the wheel ({})",
+                    radius, max_radius
+                ),
+                de_str: None,
+                value: Some(value.clone()),
+                nested_error: None,
+            });
+        }
+        let center = match center {
+            "00" => -1,
+            _ => center
+                .parse::<SlotNumber>()
+                .map_err(|e| Error::GenericError {
+                    message: format!("Failed to parse {} as i8", center),
+                    nested_error: Some(Box::new(e)),
+                })?,
+        };
+        return Ok(BetValue::Neighbours {
+            center,
+            radius: radius as usize,
+        });
+    }
 }
 
 impl TryFrom<Value> for BetValue {
@@ -89,16 +221,19 @@ impl TryFrom<Value> for BetValue {
             value: Some(value.clone()),
             nested_error: None,
         })?;
-        let bet_value = match bet_type.as_str() {
+        // the casino names of agents.json map onto the same bets
+        let bet_value = match NamingScheme::to_classic_name(bet_type.as_str()) {
             "AdjacentNumbers" => {
                 BetValue::AdjacentNumbers(AdjacentNumbers::try_from(bet_info.clone())?)
             }
+            "CallBet" => BetValue::CallBet(CallBet::try_from(bet_info.clone())?),
             "Color" => BetValue::Color(Color::try_from(bet_info.clone())?),
             "Column" => BetValue::Column(Column::try_from(bet_info.clone())?),
             "DoubleColumn" => BetValue::DoubleColumn(DoubleColumn::try_from(bet_info.clone())?),
             "Dozen" => BetValue::Dozen(Dozen::try_from(bet_info.clone())?),
             "EvenOdd" => BetValue::EvenOdd(EvenOdd::try_from(bet_info.clone())?),
             "Half" => BetValue::Half(Half::try_from(bet_info.clone())?),
+            "Neighbours" => BetValue::try_neighbours_from(bet_info)?,
             "Number" => {
                 let s = bet_info.as_str().ok_or(Error::DeserializatonError {
                     message: "Value passed onto BetValue::Number is not a valid string".to_string(),
@@ -107,7 +242,7 @@ impl TryFrom<Value> for BetValue {
                     nested_error: None,
                 })?;
                 if s == "00" {
-                    return Ok(BetValue::Number(-1 as i8));
+                    return Ok(BetValue::Number(-1_i8));
                 }
                 return s
                     .parse::<i8>()
@@ -115,7 +250,7 @@ impl TryFrom<Value> for BetValue {
                         message: format!("Failed to parse {} as i8", s),
                         nested_error: Some(Box::new(e)),
                     })
-                    .map(|n| BetValue::Number(n));
+                    .map(BetValue::Number);
             }
             "Row" => BetValue::Row(Row::try_from(bet_info.clone())?),
             _ => {
@@ -145,6 +280,7 @@ mod test {
             .get_type(),
             "Adjacent Numbers"
         );
+        assert_eq!(BetValue::CallBet(CallBet::Orphelins).get_type(), "Call Bet");
         assert_eq!(BetValue::Color(Color::Red).get_type(), "Color");
         assert_eq!(BetValue::Column(Column::One).get_type(), "Column");
         assert_eq!(
@@ -169,6 +305,10 @@ mod test {
                 numbers: vec![1, 2, 3]
             })
         );
+        assert_eq!(
+            BetValue::try_from(json!({"CallBet": "TiersDuCylindre"})).unwrap(),
+            BetValue::CallBet(CallBet::TiersDuCylindre)
+        );
         assert_eq!(
             BetValue::try_from(json!({"Color": "Red"})).unwrap(),
             BetValue::Color(Color::Red)
@@ -207,6 +347,44 @@ mod test {
             BetValue::try_from(json!({"Row": 1})).unwrap(),
             BetValue::Row(Row::One)
         );
+        assert_eq!(
+            BetValue::try_from(json!({"Street": 4})).unwrap(),
+            BetValue::Column(Column::Four)
+        );
+        assert_eq!(
+            BetValue::try_from(json!({"SixLine": [1, 2]})).unwrap(),
+            BetValue::DoubleColumn(DoubleColumn {
+                columns: [Column::One, Column::Two]
+            })
+        );
+        assert_eq!(
+            BetValue::try_from(json!({"TableColumn": 3})).unwrap(),
+            BetValue::Row(Row::Three)
+        );
+        assert_eq!(
+            BetValue::try_from(json!({"Neighbours": {"center": "17", "radius": 2}})).unwrap(),
+            BetValue::Neighbours {
+                center: 17,
+                radius: 2
+            }
+        );
+        assert!(BetValue::try_from(json!({"Neighbours": {"center": 17, "radius": 2}})).is_err());
+    }
+
+    #[test]
+    fn test_try_from_neighbours_radius() {
+        assert_eq!(
+            BetValue::try_from(json!({"Neighbours": {"center": "17", "radius": 19}})).unwrap(),
+            BetValue::Neighbours {
+                center: 17,
+                radius: 19
+            }
+        );
+        for radius in [20, u64::MAX] {
+            let result =
+                BetValue::try_from(json!({"Neighbours": {"center": "17", "radius": radius}}));
+            assert!(matches!(result, Err(Error::DeserializatonError { .. })));
+        }
     }
 
     #[test]
@@ -230,6 +408,14 @@ mod test {
         assert_eq!(BetValue::Dozen(Dozen::One).get_value_string(), "1");
         assert_eq!(BetValue::EvenOdd(EvenOdd::Even).get_value_string(), "Even");
         assert_eq!(BetValue::Half(Half::One).get_value_string(), "1");
+        assert_eq!(
+            BetValue::Neighbours {
+                center: -1,
+                radius: 2
+            }
+            .get_value_string(),
+            "00 and 2 neighbours"
+        );
         assert_eq!(BetValue::Number(1).get_value_string(), "1");
         assert_eq!(BetValue::Row(Row::One).get_value_string(), "1");
     }
@@ -1,4 +1,7 @@
+#[allow(clippy::module_inception)]
 pub mod bet;
 pub mod bet_log;
 pub mod bet_state;
+pub mod bet_trigger;
 pub mod bet_value;
+pub mod naming_scheme;
@@ -0,0 +1,82 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Casino names of bet types next to the names this project uses for them
+const CASINO_NAMES: [(&str, &str); 3] = [
+    ("Street", "Column"),
+    ("SixLine", "DoubleColumn"),
+    ("TableColumn", "Row"),
+];
+
+/// How bet types are named in the output. `Classic` keeps the names of
+/// agents.json, where a `Column` is a 3-number street and a `Row` a
+/// 12-number column, `Casino` uses the names found on real tables.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default, Deserialize, Serialize)]
+pub enum NamingScheme {
+    #[default]
+    Classic,
+    Casino,
+}
+
+impl NamingScheme {
+    /// Classic name of a bet type given under either naming scheme
+    pub fn to_classic_name(name: &str) -> &str {
+        return CASINO_NAMES
+            .iter()
+            .find(|(casino_name, _)| *casino_name == name)
+            .map(|(_, classic_name)| *classic_name)
+            .unwrap_or(name);
+    }
+
+    /// `bet_type` as returned by `BetValue::get_type`, named after this scheme
+    pub fn rename_bet_type(&self, bet_type: &str) -> String {
+        if *self == NamingScheme::Classic {
+            return bet_type.to_string();
+        }
+        return String::from(match bet_type {
+            "Column" => "Street",
+            "DoubleColumn" => "Six Line",
+            "Row" => "Table Column",
+            _ => bet_type,
+        });
+    }
+}
+
+impl fmt::Display for NamingScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                NamingScheme::Classic => "Classic",
+                NamingScheme::Casino => "Casino",
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_classic_name() {
+        assert_eq!(NamingScheme::to_classic_name("Street"), "Column");
+        assert_eq!(NamingScheme::to_classic_name("SixLine"), "DoubleColumn");
+        assert_eq!(NamingScheme::to_classic_name("TableColumn"), "Row");
+        assert_eq!(NamingScheme::to_classic_name("Column"), "Column");
+        assert_eq!(NamingScheme::to_classic_name("Dozen"), "Dozen");
+    }
+
+    #[test]
+    fn test_rename_bet_type() {
+        assert_eq!(NamingScheme::Classic.rename_bet_type("Row"), "Row");
+        assert_eq!(NamingScheme::Casino.rename_bet_type("Row"), "Table Column");
+        assert_eq!(NamingScheme::Casino.rename_bet_type("Column"), "Street");
+        assert_eq!(
+            NamingScheme::Casino.rename_bet_type("DoubleColumn"),
+            "Six Line"
+        );
+        assert_eq!(NamingScheme::Casino.rename_bet_type("Dozen"), "Dozen");
+    }
+}
@@ -1,4 +1,4 @@
-use super::slot::Slot;
+use super::{slot::Slot, wheel::Wheel};
 use crate::{
     error::Error,
     roulette::roulette_type::RouletteType,
@@ -9,13 +9,13 @@ use crate::{
 };
 use core::fmt;
 use itertools::Itertools;
-use rand::{rngs::ThreadRng, Rng};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct Board {
     pub slots: Vec<Slot>,
+    pub wheel: Wheel,
 }
 
 impl fmt::Display for Board {
@@ -62,7 +62,7 @@ impl fmt::Display for Board {
                 has_dup = true;
                 break;
             } else {
-                numbers.insert(slot.number.clone());
+                numbers.insert(slot.number);
             }
         }
         s.push_str("\nBOARD PROPERTIES\n\n");
@@ -149,14 +149,26 @@ impl fmt::Display for Board {
         s.push_str("\nNumber\n");
         s.push_str(&format!("Number of slot number: {}\n", num_of_slot_numbers));
         s.push_str(&format!("All numbers are unique? {}\n", !has_dup));
-        s.push_str(&format!("------------------------\n\n"));
+        s.push_str("------------------------\n");
+        s.push_str("\nWheel Order\n");
+        s.push_str(&format!(
+            "{}\n",
+            self.wheel
+                .pockets
+                .iter()
+                .map(|number| match number {
+                    -1 => String::from("00"),
+                    n => n.to_string(),
+                })
+                .join(" ")
+        ));
+        s.push_str("------------------------\n\n");
         return write!(f, "{}", s);
     }
 }
 
 impl Board {
     pub fn generate(roulette_type: &RouletteType) -> Result<Self, Error> {
-        let mut rng = rand::thread_rng();
         let mut slots: Vec<Slot> = Vec::new();
         slots.push(Slot {
             color: Color::Green,
@@ -178,13 +190,8 @@ impl Board {
                 column: Column::Zero,
             });
         }
-        let mut color_counter: HashMap<Color, i32> = HashMap::new();
-        color_counter.insert(Color::Red, 18);
-        color_counter.insert(Color::Black, 18);
         for n in 1..=36 {
-            let color: Color = get_color(&color_counter, &mut rng)?;
-            color_counter.entry(color).and_modify(|count| *count -= 1);
-
+            let color: Color = Color::try_from(n)?;
             let even_odd: EvenOdd = EvenOdd::try_from(n)?;
             let number: SlotNumber = n as SlotNumber;
             let dozen: Dozen = Dozen::try_from(n)?;
@@ -201,40 +208,15 @@ impl Board {
                 column,
             });
         }
-        return Ok(Board { slots });
+        let wheel = Wheel::new(roulette_type);
+        return Ok(Board { slots, wheel });
     }
 }
 
-fn get_color(color_counter: &HashMap<Color, i32>, rng: &mut ThreadRng) -> Result<Color, Error> {
-    let color: Color;
-    if color_counter.contains_key(&Color::Black) && color_counter.contains_key(&Color::Red) {
-        if color_counter[&Color::Red] <= 0 && color_counter[&Color::Black] <= 0 {
-            return Err(Error::GenericError {
-                message: format!(
-                    "There are no more Red or Black slots produce: {:?}",
-                    color_counter
-                ),
-                nested_error: None,
-            });
-        } else if color_counter[&Color::Black] <= 0 {
-            return Ok(Color::Red);
-        } else if color_counter[&Color::Red] <= 0 {
-            return Ok(Color::Black);
-        }
-    }
-    color = if rng.gen::<f64>() > 0.5 {
-        Color::Red
-    } else {
-        Color::Black
-    };
-    return Ok(color);
-}
-
 #[cfg(test)]
 mod test {
     use std::collections::{HashMap, HashSet};
 
-    use crate::error::Error;
     use crate::types::color::Color;
     use crate::types::column::Column;
     use crate::types::dozen::Dozen;
@@ -243,58 +225,36 @@ mod test {
     use crate::types::slot_number::SlotNumber;
     use crate::{roulette::roulette_type::RouletteType, types::even_odd::EvenOdd};
 
-    use super::{get_color, Board};
-
-    #[test]
-    fn test_get_color_only_red() {
-        let mut rng = rand::thread_rng();
-        let mut color_count_map = HashMap::new();
-        color_count_map.insert(Color::Red, 14);
-        color_count_map.insert(Color::Black, 0);
-        let color = get_color(&color_count_map, &mut rng);
-        assert!(color.is_ok_and(|c| c == Color::Red));
-    }
+    use super::Board;
 
     #[test]
-    fn test_get_color_only_black() {
-        let mut rng = rand::thread_rng();
-        let mut color_count_map = HashMap::new();
-        color_count_map.insert(Color::Red, 0);
-        color_count_map.insert(Color::Black, 6);
-        let result = get_color(&color_count_map, &mut rng);
-        assert!(result.is_ok_and(|c| c == Color::Black));
-    }
-
-    #[test]
-    fn test_get_color_neither_error() {
-        let mut rng = rand::thread_rng();
-        let mut color_count_map = HashMap::new();
-        color_count_map.insert(Color::Red, 0);
-        color_count_map.insert(Color::Black, 0);
-        let result = get_color(&color_count_map, &mut rng);
-        assert!(matches!(
-            result,
-            Err(Error::GenericError {
-                message: _,
-                nested_error: _
-            })
-        ));
-    }
-    #[test]
-    fn test_get_color_both_counters_have_capacity() {
-        let mut rng = rand::thread_rng();
-        let mut color_count_map = HashMap::new();
-        color_count_map.insert(Color::Red, 4);
-        color_count_map.insert(Color::Black, 6);
-        let result = get_color(&color_count_map, &mut rng);
-        assert!(result.is_ok());
+    fn test_board_generation_fixed_colors() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let red_numbers: Vec<SlotNumber> = vec![
+            1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36,
+        ];
+        for slot in board.slots.iter() {
+            let expected_color = if slot.number == 0 {
+                Color::Green
+            } else if red_numbers.contains(&slot.number) {
+                Color::Red
+            } else {
+                Color::Black
+            };
+            assert_eq!(slot.color, expected_color, "slot {}", slot.number);
+        }
+        assert_eq!(board, Board::generate(&RouletteType::European).unwrap());
     }
 
     #[test]
-    fn test_get_color_no_counter() {
-        let mut rng = rand::thread_rng();
-        let result = get_color(&HashMap::new(), &mut rng);
-        assert!(result.is_ok());
+    fn test_board_wheel_matches_slots() {
+        for roulette_type in [RouletteType::European, RouletteType::American] {
+            let board = Board::generate(&roulette_type).unwrap();
+            assert_eq!(board.wheel.pockets.len(), board.slots.len());
+            for slot in board.slots.iter() {
+                assert!(board.wheel.position_of(slot.number).is_some());
+            }
+        }
     }
 
     #[test]
@@ -344,7 +304,7 @@ mod test {
                 has_dup = true;
                 break;
             } else {
-                numbers.insert(slot.number.clone());
+                numbers.insert(slot.number);
             }
         }
 
@@ -423,7 +383,7 @@ mod test {
                 has_dup = true;
                 break;
             } else {
-                numbers.insert(slot.number.clone());
+                numbers.insert(slot.number);
             }
         }
 
@@ -1,2 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod board;
 pub mod slot;
+pub mod wheel;
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, roulette::roulette_type::RouletteType, types::slot_number::SlotNumber};
+
+const EUROPEAN_POCKETS: [SlotNumber; 37] = [
+    0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23, 10, 5, 24, 16, 33, 1, 20,
+    14, 31, 9, 22, 18, 29, 7, 28, 12, 35, 3, 26,
+];
+
+const AMERICAN_POCKETS: [SlotNumber; 38] = [
+    0, 28, 9, 26, 30, 11, 7, 20, 32, 17, 5, 22, 34, 15, 3, 24, 36, 13, 1, -1, 27, 10, 25, 29, 12,
+    8, 19, 31, 18, 6, 21, 33, 16, 4, 23, 35, 14, 2,
+];
+
+/// Physical pocket order of a wheel, listed clockwise starting from 0.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct Wheel {
+    pub pockets: Vec<SlotNumber>,
+}
+
+impl Wheel {
+    pub fn new(roulette_type: &RouletteType) -> Self {
+        let pockets = match roulette_type {
+            RouletteType::European => EUROPEAN_POCKETS.to_vec(),
+            RouletteType::American => AMERICAN_POCKETS.to_vec(),
+        };
+        return Wheel { pockets };
+    }
+
+    pub fn position_of(&self, number: SlotNumber) -> Option<usize> {
+        return self.pockets.iter().position(|pocket| *pocket == number);
+    }
+
+    /// Pockets from `first` through `last`, going clockwise
+    pub fn sector(&self, first: SlotNumber, last: SlotNumber) -> Result<Vec<SlotNumber>, Error> {
+        let find_position = |number: SlotNumber| {
+            self.position_of(number).ok_or(Error::GenericError {
+                message: format!("{} is not a pocket on the wheel", number),
+                nested_error: None,
+            })
+        };
+        let start = find_position(first)?;
+        let end = find_position(last)?;
+        let pocket_count = self.pockets.len();
+        let length = (end + pocket_count - start) % pocket_count + 1;
+        return Ok((0..length)
+            .map(|offset| self.pockets[(start + offset) % pocket_count])
+            .collect());
+    }
+
+    /// Pockets between `number` and `other_number` going whichever way round
+    /// is shorter, `None` when either isn't on the wheel
+    pub fn distance(&self, number: SlotNumber, other_number: SlotNumber) -> Option<usize> {
+        let position = self.position_of(number)?;
+        let other_position = self.position_of(other_number)?;
+        let clockwise = (other_position + self.pockets.len() - position) % self.pockets.len();
+        return Some(clockwise.min(self.pockets.len() - clockwise));
+    }
+
+    /// Returns `number` together with `radius` pockets on each side of it,
+    /// in clockwise order.
+    pub fn neighbours(&self, number: SlotNumber, radius: usize) -> Result<Vec<SlotNumber>, Error> {
+        let position = self.position_of(number).ok_or(Error::GenericError {
+            message: format!("{} is not a pocket on the wheel", number),
+            nested_error: None,
+        })?;
+        let pocket_count = self.pockets.len();
+        if radius * 2 + 1 > pocket_count {
+            return Err(Error::GenericError {
+                message: format!(
+                    "Radius {} covers more than the {} pockets on the wheel",
+                    radius, pocket_count
+                ),
+                nested_error: None,
+            });
+        }
+        let start = position + pocket_count - radius;
+        return Ok((0..=radius * 2)
+            .map(|offset| self.pockets[(start + offset) % pocket_count])
+            .collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_new_european() {
+        let wheel = Wheel::new(&RouletteType::European);
+        assert_eq!(wheel.pockets.len(), 37);
+        assert_eq!(wheel.pockets.iter().unique().count(), 37);
+        assert!(!wheel.pockets.contains(&-1));
+    }
+
+    #[test]
+    fn test_new_american() {
+        let wheel = Wheel::new(&RouletteType::American);
+        assert_eq!(wheel.pockets.len(), 38);
+        assert_eq!(wheel.pockets.iter().unique().count(), 38);
+        assert!(wheel.pockets.contains(&-1));
+    }
+
+    #[test]
+    fn test_position_of() {
+        let wheel = Wheel::new(&RouletteType::European);
+        assert_eq!(wheel.position_of(0), Some(0));
+        assert_eq!(wheel.position_of(26), Some(36));
+        assert_eq!(wheel.position_of(-1), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let wheel = Wheel::new(&RouletteType::European);
+        assert_eq!(wheel.neighbours(17, 1).unwrap(), vec![25, 17, 34]);
+        assert_eq!(wheel.neighbours(0, 2).unwrap(), vec![3, 26, 0, 32, 15]);
+        assert_eq!(wheel.neighbours(5, 0).unwrap(), vec![5]);
+    }
+
+    #[test]
+    fn test_neighbours_american_double_zero() {
+        let wheel = Wheel::new(&RouletteType::American);
+        assert_eq!(wheel.neighbours(-1, 1).unwrap(), vec![1, -1, 27]);
+        assert_eq!(wheel.neighbours(2, 1).unwrap(), vec![14, 2, 0]);
+    }
+
+    #[test]
+    fn test_sector() {
+        let wheel = Wheel::new(&RouletteType::European);
+        assert_eq!(
+            wheel.sector(12, 15).unwrap(),
+            vec![12, 35, 3, 26, 0, 32, 15]
+        );
+        assert_eq!(wheel.sector(17, 6).unwrap(), vec![17, 34, 6]);
+        assert_eq!(wheel.sector(5, 5).unwrap(), vec![5]);
+        assert!(wheel.sector(-1, 5).is_err());
+    }
+
+    #[test]
+    fn test_distance() {
+        let wheel = Wheel::new(&RouletteType::European);
+        assert_eq!(wheel.distance(17, 17), Some(0));
+        assert_eq!(wheel.distance(17, 34), Some(1));
+        assert_eq!(wheel.distance(3, 32), Some(3));
+        assert_eq!(wheel.distance(17, -1), None);
+    }
+
+    #[test]
+    fn test_neighbours_invalid() {
+        let wheel = Wheel::new(&RouletteType::European);
+        assert!(wheel.neighbours(-1, 1).is_err());
+        assert!(wheel.neighbours(17, 19).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::roulette::roulette_type::RouletteType;
+
+pub const DEFAULT_GAME_CONFIG_PATH: &str = "./res/game.json";
+pub const DEFAULT_AGENTS_PATH: &str = "./res/agents.json";
+pub const DEFAULT_SWEEP_CONFIG_PATH: &str = "./res/sweep.json";
+
+#[derive(Debug, PartialEq, Eq, Parser)]
+#[command(
+    name = "roulette_simulator",
+    version,
+    about = "Simulates roulette betting strategies"
+)]
+pub struct Cli {
+    /// Starts the HTTP server when left out
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Runs a simulation and prints its stats
+    Run(RunArgs),
+    /// Runs a simulation for every combination of the swept parameters
+    Sweep(SweepArgs),
+    /// Reports every error of an agents file, including bets that would be dropped
+    Validate(ValidateArgs),
+    /// Prints the board of a wheel type
+    Board(BoardArgs),
+    /// Starts the HTTP server
+    Serve(ServeArgs),
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        return Command::Serve(ServeArgs::default());
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Args)]
+pub struct RunArgs {
+    #[arg(long, default_value = DEFAULT_GAME_CONFIG_PATH)]
+    pub game: PathBuf,
+    #[arg(long, default_value = DEFAULT_AGENTS_PATH)]
+    pub agents: PathBuf,
+    /// Overrides `number_of_games` of the game config
+    #[arg(long)]
+    pub games: Option<i32>,
+    /// Overrides `number_of_rounds` of the game config
+    #[arg(long)]
+    pub rounds: Option<i32>,
+    /// Overrides the wheel type of the game config (european or american)
+    #[arg(long)]
+    pub wheel: Option<RouletteType>,
+    /// Writes the stats as JSON to this file instead of printing them
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+    /// Writes the games kept by the game config's `log_retention` as JSON to
+    /// this file, a summary of every game when the game config leaves it out
+    #[arg(long)]
+    pub games_output: Option<PathBuf>,
+    /// Refuses to run if any bet is invalid instead of leaving it out
+    #[arg(long)]
+    pub strict: bool,
+    /// Plays every agent on the same spins and reports their paired differences
+    #[arg(long)]
+    pub compare: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Args)]
+pub struct SweepArgs {
+    #[arg(long, default_value = DEFAULT_GAME_CONFIG_PATH)]
+    pub game: PathBuf,
+    #[arg(long, default_value = DEFAULT_AGENTS_PATH)]
+    pub agents: PathBuf,
+    #[arg(long, default_value = DEFAULT_SWEEP_CONFIG_PATH)]
+    pub sweep: PathBuf,
+    /// Writes the results as CSV to this file instead of printing them
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+    /// Refuses to run if any bet is invalid instead of leaving it out
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Args)]
+pub struct ValidateArgs {
+    #[arg(long, default_value = DEFAULT_AGENTS_PATH)]
+    pub agents: PathBuf,
+    /// Checks the bets against the wheel types of this game config
+    #[arg(long)]
+    pub game: Option<PathBuf>,
+    /// Checks the bets against this wheel type (european or american)
+    #[arg(long)]
+    pub wheel: Option<RouletteType>,
+}
+
+#[derive(Debug, PartialEq, Eq, Args)]
+pub struct BoardArgs {
+    /// Wheel type of the board (european or american)
+    #[arg(long, default_value = "european")]
+    pub wheel: RouletteType,
+}
+
+#[derive(Debug, PartialEq, Eq, Args)]
+pub struct ServeArgs {
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+}
+
+impl Default for ServeArgs {
+    fn default() -> Self {
+        return ServeArgs {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_command() {
+        let cli = Cli::try_parse_from(["roulette_simulator"]).unwrap();
+        assert_eq!(cli.command, None);
+        assert_eq!(Command::default(), Command::Serve(ServeArgs::default()));
+    }
+
+    #[test]
+    fn test_run_overrides() {
+        let cli = Cli::try_parse_from([
+            "roulette_simulator",
+            "run",
+            "--agents",
+            "./res/tst/agents.json",
+            "--games",
+            "10",
+            "--wheel",
+            "American",
+            "--compare",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Run(RunArgs {
+                game: PathBuf::from(DEFAULT_GAME_CONFIG_PATH),
+                agents: PathBuf::from("./res/tst/agents.json"),
+                games: Some(10),
+                rounds: None,
+                wheel: Some(RouletteType::American),
+                output: None,
+                games_output: None,
+                strict: false,
+                compare: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_sweep_defaults() {
+        let cli =
+            Cli::try_parse_from(["roulette_simulator", "sweep", "--output", "sweep.csv"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Sweep(SweepArgs {
+                game: PathBuf::from(DEFAULT_GAME_CONFIG_PATH),
+                agents: PathBuf::from(DEFAULT_AGENTS_PATH),
+                sweep: PathBuf::from(DEFAULT_SWEEP_CONFIG_PATH),
+                output: Some(PathBuf::from("sweep.csv")),
+                strict: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_invalid_wheel() {
+        assert!(Cli::try_parse_from(["roulette_simulator", "board", "--wheel", "french"]).is_err());
+    }
+}
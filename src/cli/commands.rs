@@ -0,0 +1,353 @@
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use serde_json::Value;
+
+use crate::api::server;
+use crate::board::board::Board;
+use crate::error::Error;
+use crate::json::{json_reader::JsonReader, load_options::LoadOptions};
+use crate::roulette::{
+    game_runner, log_retention::LogRetention, roulette_game::RouletteGame, stats::Stats,
+    sweep_runner,
+};
+
+use super::cli::{BoardArgs, Command, RunArgs, ServeArgs, SweepArgs, ValidateArgs};
+
+/// Invalid input, or the run itself failed
+const EXIT_FAILURE: u8 = 1;
+/// The run finished, but some of its games failed
+const EXIT_PARTIAL_FAILURE: u8 = 2;
+
+pub async fn execute(command: Command) -> ExitCode {
+    return match command {
+        Command::Run(args) => run(&args).await,
+        Command::Sweep(args) => sweep(&args).await,
+        Command::Validate(args) => validate(&args),
+        Command::Board(args) => board(&args),
+        Command::Serve(args) => serve(&args).await,
+    };
+}
+
+async fn run(args: &RunArgs) -> ExitCode {
+    let mut game_config = match JsonReader::read_game_json(&args.game.to_string_lossy()) {
+        Ok(game_config) => game_config,
+        Err(err) => return fail(&format!("Failed to read {}", args.game.display()), &err),
+    };
+    if let Some(games) = args.games {
+        game_config.number_of_games = games;
+    }
+    if let Some(rounds) = args.rounds {
+        game_config.number_of_rounds = rounds;
+    }
+    if let Some(wheel) = args.wheel {
+        game_config.roulette_type = Some(wheel);
+        game_config.roulette_types = None;
+    }
+    // the overrides have to make sense on their own too
+    if let Err(err) = game_config.validate() {
+        return fail(&format!("Can't play {}", args.game.display()), &err);
+    }
+    if args.compare {
+        game_config.common_random_numbers = Some(true);
+    }
+    let load_options = LoadOptions::for_game(&game_config, args.strict);
+    let agents =
+        match JsonReader::read_agents_json_with(&args.agents.to_string_lossy(), &load_options) {
+            Ok(agents) => agents,
+            Err(err) => return fail(&format!("Failed to read {}", args.agents.display()), &err),
+        };
+
+    if args.games_output.is_some() {
+        game_config
+            .log_retention
+            .get_or_insert(LogRetention::Summary);
+    }
+    let expected_number_of_games = game_config.get_total_number_of_games();
+    let run_result = match game_runner::run(game_config, agents).await {
+        Ok(run_result) => run_result,
+        Err(err) => return fail("Failed to run games", &err),
+    };
+    eprintln!(
+        "Played {} spins in {:.2?} ({:.0} spins/s)",
+        run_result.spins,
+        run_result.elapsed,
+        run_result.get_spins_per_second()
+    );
+    let stats = run_result.stats;
+    match &args.output {
+        Some(output) => {
+            if let Err(err) = write_stats_json(&stats, output) {
+                return fail(&format!("Failed to write {}", output.display()), &err);
+            }
+        }
+        None => println!("{}", stats),
+    }
+    if let Some(games_output) = &args.games_output {
+        if let Err(err) = write_games_json(&run_result.games, games_output) {
+            return fail(&format!("Failed to write {}", games_output.display()), &err);
+        }
+    }
+
+    if run_result.failed_games > 0 {
+        eprintln!(
+            "{} of {} games failed to run",
+            run_result.failed_games, expected_number_of_games
+        );
+        return ExitCode::from(EXIT_PARTIAL_FAILURE);
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn write_stats_json(stats: &Stats, output: &Path) -> Result<(), Error> {
+    let json_string = serde_json::to_string_pretty(stats).map_err(|e| Error::GenericError {
+        message: "Failed to convert stats to json".to_string(),
+        nested_error: Some(Box::new(e)),
+    })?;
+    return fs::write(output, json_string).map_err(|e| Error::IOError { nested_error: e });
+}
+
+fn write_games_json(games: &[RouletteGame], output: &Path) -> Result<(), Error> {
+    let json_string = serde_json::to_string_pretty(games).map_err(|e| Error::GenericError {
+        message: "Failed to convert games to json".to_string(),
+        nested_error: Some(Box::new(e)),
+    })?;
+    return fs::write(output, json_string).map_err(|e| Error::IOError { nested_error: e });
+}
+
+async fn sweep(args: &SweepArgs) -> ExitCode {
+    let game_config = match JsonReader::read_game_json(&args.game.to_string_lossy()) {
+        Ok(game_config) => game_config,
+        Err(err) => return fail(&format!("Failed to read {}", args.game.display()), &err),
+    };
+    let sweep_config =
+        match JsonReader::read_sweep_json(&args.sweep.to_string_lossy(), &game_config) {
+            Ok(sweep_config) => sweep_config,
+            Err(err) => return fail(&format!("Failed to read {}", args.sweep.display()), &err),
+        };
+    let load_options = LoadOptions::for_game(&game_config, args.strict);
+    let agents =
+        match JsonReader::read_agents_json_with(&args.agents.to_string_lossy(), &load_options) {
+            Ok(agents) => agents,
+            Err(err) => return fail(&format!("Failed to read {}", args.agents.display()), &err),
+        };
+
+    let sweep_result = match sweep_runner::run_sweep(game_config, agents, &sweep_config).await {
+        Ok(sweep_result) => sweep_result,
+        Err(err) => return fail("Failed to run the sweep", &err),
+    };
+    match &args.output {
+        Some(output) => {
+            if let Err(err) = fs::write(output, sweep_result.to_csv()) {
+                return fail(
+                    &format!("Failed to write {}", output.display()),
+                    &Error::IOError { nested_error: err },
+                );
+            }
+        }
+        None => println!("{}", sweep_result),
+    }
+    if sweep_result.rows.iter().any(|row| row.failed_games > 0) {
+        eprintln!("Some games of the sweep failed to run");
+        return ExitCode::from(EXIT_PARTIAL_FAILURE);
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn validate(args: &ValidateArgs) -> ExitCode {
+    let contents = match fs::read_to_string(&args.agents) {
+        Ok(contents) => contents,
+        Err(err) => {
+            return fail(
+                &format!("Failed to read {}", args.agents.display()),
+                &Error::IOError { nested_error: err },
+            )
+        }
+    };
+    let agent_entries: Value = match serde_json::from_str(&contents) {
+        Ok(agent_entries) => agent_entries,
+        Err(err) => {
+            eprintln!("{} is not valid json: {}", args.agents.display(), err);
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+    let mut roulette_types = match &args.game {
+        Some(game) => match JsonReader::read_game_json(&game.to_string_lossy()) {
+            Ok(game_config) => game_config.get_roulette_types(),
+            Err(err) => return fail(&format!("Failed to read {}", game.display()), &err),
+        },
+        None => LoadOptions::default().roulette_types,
+    };
+    if let Some(wheel) = args.wheel {
+        roulette_types = vec![wheel];
+    }
+
+    let report = JsonReader::validate_agents(&agent_entries, &roulette_types);
+    if report.is_valid() {
+        println!("{} is valid", args.agents.display());
+        return ExitCode::SUCCESS;
+    }
+    eprint!("{}", report);
+    eprintln!(
+        "{} has {} issues",
+        args.agents.display(),
+        report.issues.len()
+    );
+    return ExitCode::from(EXIT_FAILURE);
+}
+
+fn board(args: &BoardArgs) -> ExitCode {
+    return match Board::generate(&args.wheel) {
+        Ok(board) => {
+            println!("{}", board);
+            ExitCode::SUCCESS
+        }
+        Err(err) => fail("Failed to generate the board", &err),
+    };
+}
+
+async fn serve(args: &ServeArgs) -> ExitCode {
+    return match server::serve(&args.host, args.port).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => fail(
+            &format!("Failed to serve on {}:{}", args.host, args.port),
+            &Error::IOError { nested_error: err },
+        ),
+    };
+}
+
+fn fail(message: &str, err: &Error) -> ExitCode {
+    eprintln!("{}: {}", message, err);
+    return ExitCode::from(EXIT_FAILURE);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::roulette::roulette_type::RouletteType;
+
+    #[tokio::test]
+    async fn test_run_writes_json() {
+        let output = std::env::temp_dir().join("roulette_simulator_cli_test_stats.json");
+        let exit_code = run(&RunArgs {
+            game: PathBuf::from("./res/tst/game.json"),
+            agents: PathBuf::from("./res/tst/agents.json"),
+            games: Some(3),
+            rounds: Some(20),
+            wheel: Some(RouletteType::American),
+            output: Some(output.clone()),
+            games_output: None,
+            strict: false,
+            compare: true,
+        })
+        .await;
+        assert_eq!(exit_code, ExitCode::SUCCESS);
+        let stats: Value = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert!(stats["average_agent_balances"].is_object());
+        assert_eq!(stats["agent_comparisons"].as_array().unwrap().len(), 1);
+        let _ = fs::remove_file(output);
+    }
+
+    #[tokio::test]
+    async fn test_run_writes_games_json() {
+        let games_output = std::env::temp_dir().join("roulette_simulator_cli_test_games.json");
+        let exit_code = run(&RunArgs {
+            game: PathBuf::from("./res/tst/game.json"),
+            agents: PathBuf::from("./res/tst/agents.json"),
+            games: Some(3),
+            rounds: Some(20),
+            wheel: None,
+            output: None,
+            games_output: Some(games_output.clone()),
+            strict: false,
+            compare: false,
+        })
+        .await;
+        assert_eq!(exit_code, ExitCode::SUCCESS);
+        let games: Vec<RouletteGame> =
+            serde_json::from_str(&fs::read_to_string(&games_output).unwrap()).unwrap();
+        assert_eq!(games.len(), 3);
+        // the game config leaves log_retention out, so only summaries are kept
+        assert!(games.iter().all(|game| game.game_logs.is_empty()));
+        let _ = fs::remove_file(games_output);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_writes_csv() {
+        let output = std::env::temp_dir().join("roulette_simulator_cli_test_sweep.csv");
+        let exit_code = sweep(&SweepArgs {
+            game: PathBuf::from("./res/tst/game.json"),
+            agents: PathBuf::from("./res/tst/agents.json"),
+            sweep: PathBuf::from("./res/tst/sweep.json"),
+            output: Some(output.clone()),
+            strict: false,
+        })
+        .await;
+        assert_eq!(exit_code, ExitCode::SUCCESS);
+        let csv = fs::read_to_string(&output).unwrap();
+        // header plus 4 points for each of the 2 agents
+        assert_eq!(csv.lines().count(), 9);
+        assert!(csv.starts_with("balance_cents,amount_cents"));
+        let _ = fs::remove_file(output);
+    }
+
+    #[tokio::test]
+    async fn test_run_missing_file() {
+        let exit_code = run(&RunArgs {
+            game: PathBuf::from("./res/tst/non_existing.json"),
+            agents: PathBuf::from("./res/tst/agents.json"),
+            games: None,
+            rounds: None,
+            wheel: None,
+            output: None,
+            games_output: None,
+            strict: false,
+            compare: false,
+        })
+        .await;
+        assert_eq!(exit_code, ExitCode::from(EXIT_FAILURE));
+    }
+
+    #[tokio::test]
+    async fn test_run_strict() {
+        // the agent holds four numbers that don't make a corner
+        let exit_code = run(&RunArgs {
+            game: PathBuf::from("./res/tst/game.json"),
+            agents: PathBuf::from("./res/tst/agents_invalid_bet.json"),
+            games: Some(1),
+            rounds: Some(1),
+            wheel: None,
+            output: None,
+            games_output: None,
+            strict: true,
+            compare: false,
+        })
+        .await;
+        assert_eq!(exit_code, ExitCode::from(EXIT_FAILURE));
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = validate(&ValidateArgs {
+            agents: PathBuf::from("./res/agents.json"),
+            game: Some(PathBuf::from("./res/game.json")),
+            wheel: None,
+        });
+        assert_eq!(valid, ExitCode::SUCCESS);
+        let invalid = validate(&ValidateArgs {
+            agents: PathBuf::from("./res/tst/agents_invalid_bet.json"),
+            game: None,
+            wheel: Some(RouletteType::American),
+        });
+        assert_eq!(invalid, ExitCode::from(EXIT_FAILURE));
+        let missing = validate(&ValidateArgs {
+            agents: PathBuf::from("./res/tst/non_existing.json"),
+            game: None,
+            wheel: None,
+        });
+        assert_eq!(missing, ExitCode::from(EXIT_FAILURE));
+    }
+}
@@ -2,6 +2,9 @@ use core::fmt;
 use serde_json::Value;
 use std::any::Any;
 
+use crate::json::validation_report::ValidationReport;
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum Error {
     DeserializatonError {
@@ -26,6 +29,25 @@ pub enum Error {
     IOError {
         nested_error: std::io::Error,
     },
+    ValidationError {
+        report: ValidationReport,
+    },
+}
+
+impl Error {
+    /// The message alone, without the values and nested errors Display appends
+    pub fn get_message(&self) -> String {
+        return match self {
+            Error::DeserializatonError { message, .. }
+            | Error::FromStrError { message, .. }
+            | Error::GenericError { message, .. }
+            | Error::JoinError { message, .. } => message.clone(),
+            Error::IOError { nested_error } => nested_error.to_string(),
+            Error::ValidationError { report } => {
+                format!("Validation failed with {} issues", report.issues.len())
+            }
+        };
+    }
 }
 
 impl fmt::Display for Error {
@@ -73,6 +95,10 @@ impl fmt::Display for Error {
             } => {
                 s.push_str(&format!("IO Error: {}\n", io_error));
             }
+            Error::ValidationError { report } => {
+                s.push_str(&format!("{}\n", self.get_message()));
+                s.push_str(&report.to_string());
+            }
         }
         return write!(f, "{}", s);
     }
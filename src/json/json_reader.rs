@@ -3,136 +3,365 @@ use std::{fs::File, io::Read};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tracing::warn;
 
 use crate::{
-    agent::{agent::Agent, agent_log::AgentLog},
-    bet::{bet::Bet, bet_log::BetLog, bet_state::BetState, bet_value::BetValue},
-    roulette::{game_configs::GameConfig, roulette_type::RouletteType},
+    agent::{agent::Agent, exit_rules::ExitRules},
+    bet::{bet::Bet, bet_state::BetState, bet_trigger::BetTrigger, bet_value::BetValue},
+    roulette::{game_configs::GameConfig, roulette_type::RouletteType, sweep_config::SweepConfig},
+    strategy::{strategy::Strategy, strategy_type::StrategyType},
 };
 
+use super::{
+    load_options::LoadOptions,
+    validation_report::{ValidationIssue, ValidationReport},
+};
+
+/// Progression factor of bets that don't set one, doubling after a loss
+const DEFAULT_PROGRESSION_FACTOR: i64 = 2;
+/// Root of the paths in validation reports of agent files
+const AGENTS_PATH: &str = "agents";
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
 pub struct JsonReader {}
 
 impl JsonReader {
     pub fn read_game_json(game_config_path: &str) -> Result<GameConfig, Error> {
-        let mut file =
-            File::open(game_config_path).map_err(|e| Error::IOError { nested_error: e })?;
-        let mut contents = String::new();
-        let _ = file.read_to_string(&mut contents);
-        let mut game_config: GameConfig =
+        let contents = Self::read_file(game_config_path)?;
+        let game_config_entry: Value =
             serde_json::from_str(&contents).map_err(|e| Error::DeserializatonError {
                 message: format!("Failed to deserialize game config json file: {}", e),
                 de_str: Some(contents.clone()),
                 value: None,
                 nested_error: Some(Box::new(e)),
             })?;
+        return Self::parse_game_config(game_config_entry);
+    }
+
+    pub fn parse_game_config(game_config_entry: Value) -> Result<GameConfig, Error> {
+        let mut game_config: GameConfig = serde_json::from_value(game_config_entry.clone())
+            .map_err(|e| Error::DeserializatonError {
+                message: format!("Failed to deserialize game config: {}", e),
+                de_str: None,
+                value: Some(game_config_entry),
+                nested_error: Some(Box::new(e)),
+            })?;
         if game_config.roulette_type.is_none() {
             game_config.roulette_type = Some(RouletteType::European);
         }
+        game_config.validate()?;
+        if let Some(table_limits) = &game_config.table_limits {
+            table_limits.validate()?;
+        }
+        if let Some(payout_table) = &game_config.payout_table {
+            payout_table.validate()?;
+        }
         return Ok(game_config);
     }
 
+    /// The sweep is checked against the games `game_config` plays at every point
+    pub fn read_sweep_json(
+        sweep_config_path: &str,
+        game_config: &GameConfig,
+    ) -> Result<SweepConfig, Error> {
+        let contents = Self::read_file(sweep_config_path)?;
+        let sweep_config_entry: Value =
+            serde_json::from_str(&contents).map_err(|e| Error::DeserializatonError {
+                message: format!("Failed to deserialize sweep config json file: {}", e),
+                de_str: Some(contents.clone()),
+                value: None,
+                nested_error: Some(Box::new(e)),
+            })?;
+        return Self::parse_sweep_config(sweep_config_entry, game_config);
+    }
+
+    pub fn parse_sweep_config(
+        sweep_config_entry: Value,
+        game_config: &GameConfig,
+    ) -> Result<SweepConfig, Error> {
+        let sweep_config: SweepConfig = serde_json::from_value(sweep_config_entry.clone())
+            .map_err(|e| Error::DeserializatonError {
+                message: format!("Failed to deserialize sweep config: {}", e),
+                de_str: None,
+                value: Some(sweep_config_entry),
+                nested_error: Some(Box::new(e)),
+            })?;
+        sweep_config.validate(game_config)?;
+        return Ok(sweep_config);
+    }
+
     pub fn read_agents_json(agents_path: &str) -> Result<Vec<Agent>, Error> {
-        let mut file = File::open(agents_path).map_err(|e| Error::IOError { nested_error: e })?;
-        let mut contents = String::new();
-        let _ = file.read_to_string(&mut contents);
-        let agent_entries: Vec<Value> =
+        return Self::read_agents_json_with(agents_path, &LoadOptions::default());
+    }
+
+    pub fn read_agents_json_with(
+        agents_path: &str,
+        load_options: &LoadOptions,
+    ) -> Result<Vec<Agent>, Error> {
+        let contents = Self::read_file(agents_path)?;
+        let agent_entries: Value =
             serde_json::from_str(&contents).map_err(|e| Error::DeserializatonError {
                 message: format!("Failed to deserialize agents json file: {}", e),
                 de_str: Some(contents.clone()),
                 value: None,
                 nested_error: Some(Box::new(e)),
             })?;
+        return Self::parse_agents_with(agent_entries, load_options);
+    }
+
+    /// Loads the agents, leaving out invalid bets unless `load_options` is strict.
+    /// Agents that can't be built at all always fail the whole load.
+    pub fn parse_agents_with(
+        agents_entry: Value,
+        load_options: &LoadOptions,
+    ) -> Result<Vec<Agent>, Error> {
+        let (agents, report) = match Self::load_agents(&agents_entry, &load_options.roulette_types)
+        {
+            Ok(loaded) => loaded,
+            Err(report) => return Err(Error::ValidationError { report }),
+        };
+        if report.is_valid() {
+            return Ok(agents);
+        }
+        if load_options.strict {
+            return Err(Error::ValidationError { report });
+        }
+        for issue in report.issues.iter() {
+            warn!("Leaving out invalid bet, {}", issue);
+        }
+        return Ok(agents);
+    }
+
+    /// Every problem of the agents on any of the `roulette_types` wheels
+    pub fn validate_agents(
+        agents_entry: &Value,
+        roulette_types: &[RouletteType],
+    ) -> ValidationReport {
+        return match Self::load_agents(agents_entry, roulette_types) {
+            Ok((_, report)) => report,
+            Err(report) => report,
+        };
+    }
+
+    /// The agents without their invalid bets and the report of every issue,
+    /// or only the report if an agent couldn't be built
+    fn load_agents(
+        agents_entry: &Value,
+        roulette_types: &[RouletteType],
+    ) -> Result<(Vec<Agent>, ValidationReport), ValidationReport> {
+        let mut report = ValidationReport::new();
+        let agent_entries: &Vec<Value> = match agents_entry.as_array() {
+            Some(agent_entries) => agent_entries,
+            None => {
+                report.push(ValidationIssue::new(
+                    AGENTS_PATH,
+                    "Agents must be a json array",
+                ));
+                return Err(report);
+            }
+        };
         let mut agents: Vec<Agent> = Vec::new();
-        let mut agent_number: i32 = 1;
-
-        for agent_entry in agent_entries {
-            let agent_logs: Vec<AgentLog> = Vec::new();
-            let balance_cents: i64 =
-                agent_entry["balance_cents"]
-                    .as_i64()
-                    .ok_or(Error::DeserializatonError {
-                        message: format!(
-                            "Failed to deserialize balance cents from {}",
-                            agent_entry
-                        ),
-                        de_str: None,
-                        value: Some(agent_entry.clone()),
-                        nested_error: None,
-                    })?;
-            let strategic_bets_data: &Vec<Value> =
-                agent_entry["strategic_bets"]
-                    .as_array()
-                    .ok_or(Error::DeserializatonError {
-                        message: format!(
-                            "Failed to deserialize strategic bets from {}",
-                            agent_entry
-                        ),
-                        de_str: None,
-                        value: Some(agent_entry.clone()),
-                        nested_error: None,
-                    })?;
-            let mut strategic_bets: Vec<Bet> = Vec::new();
-            let name_entry: Option<&str> = agent_entry["name"].as_str();
-            let name: String = name_entry
-                .unwrap_or(&format!("Agent {}", agent_number))
-                .to_string();
-
-            for strategic_bet_data in strategic_bets_data {
-                let bet_value: BetValue = strategic_bet_data["bet_value"].clone().try_into()?;
-                let amount_cents: i64 = strategic_bet_data["amount_cents"].as_i64().ok_or(
-                    Error::DeserializatonError {
-                        message: format!(
-                            "Failed to deserialize amount cents from {}",
-                            strategic_bet_data
-                        ),
-                        de_str: None,
-                        value: Some(strategic_bet_data.clone()),
-                        nested_error: None,
-                    },
-                )?;
-                let initial_amount_cents: i64 = amount_cents;
-                let progression_factor: i64 = strategic_bet_data["progression_factor"]
-                    .as_i64()
-                    .ok_or(Error::DeserializatonError {
-                        message: format!(
-                            "Failed to deserialize progression factor from {}",
-                            strategic_bet_data
-                        ),
-                        de_str: None,
-                        value: Some(strategic_bet_data.clone()),
-                        nested_error: None,
-                    })?;
-                let bet_state: BetState = BetState::Active;
-                let bet_logs: Vec<BetLog> = Vec::new();
-                let mut bet = Bet {
-                    bet_state,
-                    bet_value,
-                    amount_cents,
-                    initial_amount_cents,
-                    progression_factor,
-                    bet_logs,
-                };
-                bet.validate(None);
-                if bet.bet_state == BetState::Active {
-                    strategic_bets.push(bet);
+        let mut all_agents_loaded = true;
+        for (agent_index, agent_entry) in agent_entries.iter().enumerate() {
+            let path = format!("{}[{}]", AGENTS_PATH, agent_index);
+            match Self::load_agent(agent_entry, agent_index, roulette_types, &mut report) {
+                Ok(agent) => agents.push(agent),
+                Err(issues) => {
+                    all_agents_loaded = false;
+                    for issue in issues {
+                        report.push(issue.nested_under(&path));
+                    }
                 }
             }
-            agents.push(Agent {
-                balance_cents,
-                strategic_bets,
-                name,
-                agent_logs,
-            });
-            agent_number += 1;
         }
-        return Ok(agents);
+        if !all_agents_loaded {
+            return Err(report);
+        }
+        return Ok((agents, report));
+    }
+
+    /// Issues of bets go straight into `report` as the agent can do without
+    /// them, issues of the agent itself are returned with paths relative to it
+    fn load_agent(
+        agent_entry: &Value,
+        agent_index: usize,
+        roulette_types: &[RouletteType],
+        report: &mut ValidationReport,
+    ) -> Result<Agent, Vec<ValidationIssue>> {
+        let mut issues: Vec<ValidationIssue> = Vec::new();
+        let balance_cents: Option<i64> = agent_entry["balance_cents"].as_i64();
+        if balance_cents.is_none() {
+            issues.push(ValidationIssue::new(
+                "balance_cents",
+                "Balance cents must be an integer",
+            ));
+        }
+        let strategic_bets_data = agent_entry["strategic_bets"].as_array();
+        if strategic_bets_data.is_none() {
+            issues.push(ValidationIssue::new(
+                "strategic_bets",
+                "Strategic bets must be a json array",
+            ));
+        }
+        let exit_rules: ExitRules = if agent_entry["exit_rules"].is_null() {
+            ExitRules::default()
+        } else {
+            serde_json::from_value(agent_entry["exit_rules"].clone()).unwrap_or_else(|e| {
+                issues.push(ValidationIssue::new(
+                    "exit_rules",
+                    &format!("Failed to deserialize exit rules: {}", e),
+                ));
+                ExitRules::default()
+            })
+        };
+        issues.extend(
+            exit_rules
+                .find_issues()
+                .into_iter()
+                .map(|issue| issue.nested_under("exit_rules")),
+        );
+        let name_entry = &agent_entry["name"];
+        if !name_entry.is_null() && !name_entry.is_string() {
+            issues.push(ValidationIssue::new("name", "Name must be a string"));
+        }
+        let name: String = name_entry
+            .as_str()
+            .unwrap_or(&format!("Agent {}", agent_index + 1))
+            .to_string();
+
+        let mut strategic_bets: Vec<Bet> = Vec::new();
+        for (bet_index, strategic_bet_data) in strategic_bets_data.into_iter().flatten().enumerate()
+        {
+            let path = format!(
+                "{}[{}].strategic_bets[{}]",
+                AGENTS_PATH, agent_index, bet_index
+            );
+            let bet_issues = match Self::load_bet(strategic_bet_data) {
+                Ok(bet) => {
+                    let mut bet_issues: Vec<ValidationIssue> = Vec::new();
+                    for roulette_type in roulette_types {
+                        bet_issues.extend(bet.find_issues(roulette_type));
+                    }
+                    if bet_issues.is_empty() {
+                        strategic_bets.push(bet);
+                    }
+                    bet_issues
+                }
+                Err(bet_issues) => bet_issues,
+            };
+            for issue in bet_issues {
+                report.push(issue.nested_under(&path));
+            }
+        }
+
+        let balance_cents = match balance_cents {
+            Some(balance_cents) if issues.is_empty() => balance_cents,
+            _ => return Err(issues),
+        };
+        return Ok(Agent {
+            balance_cents,
+            strategic_bets,
+            name,
+            agent_logs: Vec::new(),
+            exit_rules,
+            starting_balance_cents: balance_cents,
+            peak_balance_cents: balance_cents,
+            exit: None,
+        });
+    }
+
+    /// Builds the bet as written, every field that can't be read is an issue
+    fn load_bet(strategic_bet_data: &Value) -> Result<Bet, Vec<ValidationIssue>> {
+        let mut issues: Vec<ValidationIssue> = Vec::new();
+        let bet_value: Option<BetValue> =
+            match BetValue::try_from(strategic_bet_data["bet_value"].clone()) {
+                Ok(bet_value) => Some(bet_value),
+                Err(e) => {
+                    issues.push(ValidationIssue::new("bet_value", &e.get_message()));
+                    None
+                }
+            };
+        let amount_cents: Option<i64> = strategic_bet_data["amount_cents"].as_i64();
+        if amount_cents.is_none() {
+            issues.push(ValidationIssue::new(
+                "amount_cents",
+                "Amount cents must be an integer",
+            ));
+        }
+        let progression_factor_data = &strategic_bet_data["progression_factor"];
+        let progression_factor: i64 = if progression_factor_data.is_null() {
+            DEFAULT_PROGRESSION_FACTOR
+        } else {
+            progression_factor_data.as_i64().unwrap_or_else(|| {
+                issues.push(ValidationIssue::new(
+                    "progression_factor",
+                    "Progression factor must be an integer",
+                ));
+                DEFAULT_PROGRESSION_FACTOR
+            })
+        };
+        let strategy_data = &strategic_bet_data["strategy"];
+        let strategy: StrategyType = if strategy_data.is_null() {
+            StrategyType::default()
+        } else {
+            StrategyType::try_from(strategy_data.clone()).unwrap_or_else(|e| {
+                issues.push(ValidationIssue::new("strategy", &e.get_message()));
+                StrategyType::default()
+            })
+        };
+
+        let trigger_data = &strategic_bet_data["trigger"];
+        let trigger: Option<BetTrigger> = if trigger_data.is_null() {
+            None
+        } else {
+            BetTrigger::try_from(trigger_data.clone())
+                .map_err(|e| issues.push(ValidationIssue::new("trigger", &e.get_message())))
+                .ok()
+        };
+
+        let (bet_value, initial_amount_cents) = match (bet_value, amount_cents) {
+            (Some(bet_value), Some(amount_cents)) if issues.is_empty() => (bet_value, amount_cents),
+            _ => return Err(issues),
+        };
+        let amount_cents = strategy.opening_amount_cents(initial_amount_cents);
+        return Ok(Bet {
+            bet_state: BetState::Active,
+            bet_value,
+            amount_cents,
+            initial_amount_cents,
+            payout_cents: 0,
+            progression_factor,
+            strategy,
+            table_limit_hit: false,
+            bet_logs: Vec::new(),
+            trigger,
+        });
+    }
+
+    fn read_file(path: &str) -> Result<String, Error> {
+        let mut file = File::open(path).map_err(|e| Error::IOError { nested_error: e })?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .map_err(|e| Error::IOError { nested_error: e })?;
+        return Ok(contents);
     }
 }
 
 #[cfg(test)]
 mod test {
+    use serde_json::{json, Value};
+
     use super::JsonReader;
+    use crate::agent::exit_rules::ExitRules;
+    use crate::bet::{bet_trigger::BetTrigger, bet_value::BetValue};
+    use crate::error::Error;
+    use crate::json::load_options::LoadOptions;
+    use crate::json::validation_report::ValidationIssue;
+    use crate::roulette::roulette_type::RouletteType;
+    use crate::strategy::{
+        fibonacci::Fibonacci, labouchere::Labouchere, strategy_type::StrategyType,
+    };
+    use crate::types::color::Color;
 
     #[test]
     fn test_game_json_reading_success() {
@@ -146,12 +375,282 @@ mod test {
         assert!(result.is_err());
     }
 
+    fn create_game_config_entry(number_of_games: i32, number_of_rounds: i32) -> Value {
+        return json!({
+            "number_of_rounds": number_of_rounds,
+            "number_of_games": number_of_games,
+            "allow_negative_balance": false
+        });
+    }
+
+    #[test]
+    fn test_parse_game_config_number_of_games() {
+        assert!(JsonReader::parse_game_config(create_game_config_entry(1, 10)).is_ok());
+        assert!(JsonReader::parse_game_config(create_game_config_entry(0, 10)).is_err());
+        assert!(JsonReader::parse_game_config(create_game_config_entry(-3, 10)).is_err());
+    }
+
+    #[test]
+    fn test_parse_game_config_number_of_rounds() {
+        assert!(JsonReader::parse_game_config(create_game_config_entry(10, 1)).is_ok());
+        assert!(JsonReader::parse_game_config(create_game_config_entry(10, 0)).is_err());
+        assert!(JsonReader::parse_game_config(create_game_config_entry(10, -1)).is_err());
+    }
+
+    #[test]
+    fn test_parse_game_config_surrender() {
+        let parse = |game_config: Value| {
+            let mut entry = create_game_config_entry(10, 10);
+            entry
+                .as_object_mut()
+                .unwrap()
+                .extend(game_config.as_object().unwrap().clone());
+            JsonReader::parse_game_config(entry)
+        };
+        assert!(
+            parse(json!({"roulette_type": "American", "even_money_rule": "Surrender"})).is_ok()
+        );
+        assert!(parse(json!({"even_money_rule": "Surrender"})).is_err());
+        assert!(
+            parse(json!({"roulette_type": "European", "even_money_rule": "Surrender"})).is_err()
+        );
+        assert!(parse(json!({
+            "roulette_types": ["American", "European"],
+            "even_money_rule": "Surrender"
+        }))
+        .is_err());
+        assert!(parse(json!({
+            "roulette_type": "American",
+            "even_money_rules": ["Standard", "Surrender"]
+        }))
+        .is_ok());
+        assert!(parse(json!({
+            "roulette_type": "European",
+            "even_money_rules": ["LaPartage", "Surrender"]
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_sweep_json_reading() {
+        let game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        let sweep_config =
+            JsonReader::read_sweep_json("./res/tst/sweep.json", &game_config).unwrap();
+        assert_eq!(sweep_config.get_number_of_points(), 4);
+        assert!(
+            JsonReader::parse_sweep_config(json!({"amount_cents": "100"}), &game_config).is_err()
+        );
+        assert!(
+            JsonReader::parse_sweep_config(json!({"amount_cents": [-5]}), &game_config).is_err()
+        );
+    }
+
     #[test]
     fn test_agent_json_reading_success() {
         let result = JsonReader::read_agents_json("./res/tst/agents.json");
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_agent_json_reading_strategies() {
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let bets = &agents[1].strategic_bets;
+        assert_eq!(bets[0].strategy, StrategyType::default());
+        assert_eq!(
+            bets[1].strategy,
+            StrategyType::Fibonacci(Fibonacci::default())
+        );
+        assert_eq!(bets[1].progression_factor, 2);
+        assert_eq!(
+            bets[3].strategy,
+            StrategyType::Labouchere(Labouchere::new(vec![1, 2, 3]))
+        );
+        assert_eq!(bets[3].initial_amount_cents, 500);
+        assert_eq!(bets[3].amount_cents, 2000);
+    }
+
+    #[test]
+    fn test_agent_json_reading_exit_rules() {
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        assert_eq!(agents[0].exit_rules, ExitRules::default());
+        assert_eq!(
+            agents[1].exit_rules,
+            ExitRules {
+                stop_loss_cents: Some(50000),
+                take_profit_cents: Some(100000),
+                max_spins: Some(200),
+                trailing_stop_cents: Some(25000),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_json() {
+        let game_config = JsonReader::parse_game_config(json!({
+            "number_of_rounds": 10,
+            "number_of_games": 2,
+            "allow_negative_balance": false
+        }))
+        .unwrap();
+        assert_eq!(game_config.roulette_type, Some(RouletteType::European));
+        let agents = JsonReader::parse_agents_with(
+            json!([{
+                "balance_cents": 10000,
+                "strategic_bets": [{"bet_value": {"Color": "Red"}, "amount_cents": 100}]
+            }]),
+            &LoadOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(agents[0].name, "Agent 1");
+        assert_eq!(agents[0].strategic_bets.len(), 1);
+        assert!(JsonReader::parse_agents_with(
+            json!({"balance_cents": 10000}),
+            &LoadOptions::default()
+        )
+        .is_err());
+        assert!(JsonReader::parse_game_config(json!({"number_of_rounds": 10})).is_err());
+    }
+
+    #[test]
+    fn test_parse_agents_with_trigger() {
+        let agents = JsonReader::parse_agents_with(
+            json!([{
+                "balance_cents": 10000,
+                "strategic_bets": [{
+                    "bet_value": {"Color": "Black"},
+                    "amount_cents": 100,
+                    "trigger": {"Streak": {"of": {"Color": "Red"}, "length": 5}}
+                }]
+            }]),
+            &LoadOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            agents[0].strategic_bets[0].trigger,
+            Some(BetTrigger::Streak {
+                of: BetValue::Color(Color::Red),
+                length: 5
+            })
+        );
+        assert!(JsonReader::parse_agents_with(
+            json!([{
+                "balance_cents": 10000,
+                "strategic_bets": [{
+                    "bet_value": {"Color": "Black"},
+                    "amount_cents": 100,
+                    "trigger": {"Hot": {"of": {"Number": "7"}, "window": 5, "min_hits": 6}}
+                }]
+            }]),
+            &LoadOptions {
+                strict: true,
+                ..LoadOptions::default()
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_agents_collects_every_issue() {
+        let agents_entry = json!([
+            {
+                "balance_cents": 10000,
+                "strategic_bets": [
+                    {"bet_value": {"Color": "Red"}, "amount_cents": 100},
+                    {"bet_value": {"Number": "00"}, "amount_cents": 100},
+                    {"bet_value": {"Column": 13}, "amount_cents": 100},
+                    {"bet_value": {"AdjacentNumbers": ["1", "5"]}, "amount_cents": 0},
+                    {"bet_value": {"Row": 2}, "amount_cents": 100, "strategy": "Unknown"}
+                ]
+            },
+            {"strategic_bets": "Red"}
+        ]);
+        let report = JsonReader::validate_agents(&agents_entry, &[RouletteType::European]);
+        let paths: Vec<&str> = report
+            .issues
+            .iter()
+            .map(|issue| issue.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "agents[0].strategic_bets[1].bet_value",
+                "agents[0].strategic_bets[2].bet_value",
+                "agents[0].strategic_bets[3].bet_value",
+                "agents[0].strategic_bets[3].amount_cents",
+                "agents[0].strategic_bets[4].strategy",
+                "agents[1].balance_cents",
+                "agents[1].strategic_bets",
+            ]
+        );
+        assert!(!report.valid);
+    }
+
+    #[test]
+    fn test_validate_agents_exit_rules() {
+        let agents_entry = json!([{
+            "balance_cents": 10000,
+            "strategic_bets": [{"bet_value": {"Color": "Red"}, "amount_cents": 100}],
+            "exit_rules": {"stop_loss_cents": -500, "max_spins": 0, "trailing_stop_cents": 200}
+        }]);
+        let report = JsonReader::validate_agents(&agents_entry, &[RouletteType::European]);
+        assert_eq!(
+            report.issues,
+            vec![
+                ValidationIssue::new(
+                    "agents[0].exit_rules.stop_loss_cents",
+                    "Must be positive, got -500"
+                ),
+                ValidationIssue::new("agents[0].exit_rules.max_spins", "Must be positive, got 0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_agents_by_roulette_type() {
+        let agents_entry = json!([{
+            "balance_cents": 10000,
+            "strategic_bets": [{"bet_value": {"AdjacentNumbers": ["00", "3"]}, "amount_cents": 100}]
+        }]);
+        let report = JsonReader::validate_agents(&agents_entry, &[RouletteType::American]);
+        assert!(report.is_valid());
+        let report = JsonReader::validate_agents(
+            &agents_entry,
+            &[RouletteType::American, RouletteType::European],
+        );
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(
+            report.issues[0].message,
+            "00 is not on the European wheel".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_agents_strict() {
+        let agents_entry = json!([{
+            "balance_cents": 10000,
+            "strategic_bets": [
+                {"bet_value": {"Color": "Red"}, "amount_cents": 100},
+                {"bet_value": {"DoubleColumn": [1, 3]}, "amount_cents": 100}
+            ]
+        }]);
+        let agents =
+            JsonReader::parse_agents_with(agents_entry.clone(), &LoadOptions::default()).unwrap();
+        assert_eq!(agents[0].strategic_bets.len(), 1);
+        let strict_load_options = LoadOptions {
+            strict: true,
+            ..LoadOptions::default()
+        };
+        match JsonReader::parse_agents_with(agents_entry, &strict_load_options) {
+            Err(Error::ValidationError { report }) => {
+                assert_eq!(
+                    report.issues[0].path,
+                    "agents[0].strategic_bets[1].bet_value"
+                );
+            }
+            result => panic!("Expected a validation error, got {:?}", result),
+        }
+    }
+
     #[test]
     fn test_agent_json_reading_failure() {
         let result = JsonReader::read_agents_json("./res/tst/non_existing.json");
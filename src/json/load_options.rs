@@ -0,0 +1,28 @@
+use crate::roulette::{game_configs::GameConfig, roulette_type::RouletteType};
+
+/// How agents are checked while they are loaded
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LoadOptions {
+    /// Wheels the agents will play on, a bet has to be valid on all of them
+    pub roulette_types: Vec<RouletteType>,
+    /// Rejects the whole file on any issue instead of dropping the invalid bets
+    pub strict: bool,
+}
+
+impl LoadOptions {
+    pub fn for_game(game_config: &GameConfig, strict: bool) -> Self {
+        return LoadOptions {
+            roulette_types: game_config.get_roulette_types(),
+            strict,
+        };
+    }
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        return LoadOptions {
+            roulette_types: vec![RouletteType::European],
+            strict: false,
+        };
+    }
+}
@@ -1,2 +1,4 @@
 pub mod deserializable;
 pub mod json_reader;
+pub mod load_options;
+pub mod validation_report;
@@ -0,0 +1,96 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// A single problem of a config, e.g. `agents[1].strategic_bets[3].bet_value`
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn new(path: &str, message: &str) -> Self {
+        return ValidationIssue {
+            path: path.to_string(),
+            message: message.to_string(),
+        };
+    }
+
+    /// Moves the issue under `parent`, e.g. `bet_value` under `agents[0].strategic_bets[2]`
+    pub fn nested_under(mut self, parent: &str) -> Self {
+        self.path = format!("{}.{}", parent, self.path);
+        return self;
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}: {}", self.path, self.message);
+    }
+}
+
+/// Every problem found in a config, instead of only the first one
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn new() -> Self {
+        return ValidationReport {
+            valid: true,
+            issues: Vec::new(),
+        };
+    }
+
+    /// Adds `issue` unless the same one was already reported
+    pub fn push(&mut self, issue: ValidationIssue) {
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+        self.valid = false;
+    }
+
+    pub fn is_valid(&self) -> bool {
+        return self.issues.is_empty();
+    }
+}
+
+impl Default for ValidationReport {
+    fn default() -> Self {
+        return ValidationReport::new();
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = String::new();
+        for issue in self.issues.iter() {
+            s.push_str(&format!("{}\n", issue));
+        }
+        return write!(f, "{}", s);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_push() {
+        let mut report = ValidationReport::new();
+        assert!(report.is_valid());
+        let issue = ValidationIssue::new("bet_value", "00 is only on American wheels")
+            .nested_under("agents[0].strategic_bets[1]");
+        report.push(issue.clone());
+        report.push(issue);
+        assert!(!report.is_valid());
+        assert!(!report.valid);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(
+            report.to_string(),
+            "agents[0].strategic_bets[1].bet_value: 00 is only on American wheels\n"
+        );
+    }
+}
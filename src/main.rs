@@ -1,13 +1,17 @@
-use actix_web::{App, HttpServer};
-use api::endpoints::run_with_files;
+use std::process::ExitCode;
+
+use clap::Parser;
+use cli::{cli::Cli, commands};
 
 mod agent;
 mod api;
 mod bet;
 mod board;
+mod cli;
 mod error;
 mod json;
 mod roulette;
+mod strategy;
 mod types;
 
 use tracing_appender::non_blocking::WorkerGuard;
@@ -16,15 +20,14 @@ use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::{fmt, layer::SubscriberExt, registry::Registry};
 
 #[actix_web::main]
-async fn main() -> std::io::Result<()> {
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let command = cli.command.unwrap_or_default();
     let (file_guard, json_file_guard) = set_up_logging();
-    let _ = HttpServer::new(|| App::new().service(run_with_files))
-        .bind(("127.0.0.1", 8080))?
-        .run()
-        .await;
+    let exit_code = commands::execute(command).await;
     drop(file_guard);
     drop(json_file_guard);
-    Ok(())
+    return exit_code;
 }
 
 fn set_up_logging() -> (WorkerGuard, WorkerGuard) {
@@ -34,14 +37,15 @@ fn set_up_logging() -> (WorkerGuard, WorkerGuard) {
     let (non_blocking_json_file, json_file_guard) =
         tracing_appender::non_blocking(json_file_appender);
 
-    let stdout_layer = fmt::layer().with_writer(|| std::io::stdout());
+    // stdout is left to command output, e.g. the stats printed by `run`
+    let stderr_layer = fmt::layer().with_writer(std::io::stderr);
     let file_layer = fmt::layer().with_writer(move || non_blocking_file.clone());
     let json_file_layer = fmt::layer()
         .json()
         .with_writer(move || non_blocking_json_file.clone());
 
     let subscriber = Registry::default()
-        .with(stdout_layer)
+        .with(stderr_layer)
         .with(file_layer)
         .with(json_file_layer)
         .with(LevelFilter::DEBUG);
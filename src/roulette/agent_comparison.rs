@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::{distribution::RunningMoments, roulette_game::RouletteGame};
+
+/// Paired difference of the final balances of two agents that played the same
+/// spins, `challenger` minus `baseline` in every game
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AgentComparison {
+    pub baseline: String,
+    pub challenger: String,
+    pub number_of_games: i64,
+    pub mean_difference_cents: f64,
+    /// Standard error of the mean difference, from the per-game differences
+    pub paired_standard_error_cents: f64,
+    /// Standard error the mean difference would have if the agents had played
+    /// independent spins
+    pub unpaired_standard_error_cents: f64,
+    /// Share of the variance of the mean difference the pairing removed
+    pub variance_reduction: f64,
+    pub t_statistic: f64,
+    /// Two-sided, taken from the normal distribution which the t distribution
+    /// is close to at the game counts of a run
+    pub p_value: f64,
+}
+
+/// Running moments of the final balances of two agents and of their difference
+#[derive(Debug, PartialEq, Clone, Default)]
+struct PairTotals {
+    differences_cents: RunningMoments,
+    baseline_balances_cents: RunningMoments,
+    challenger_balances_cents: RunningMoments,
+}
+
+impl PairTotals {
+    fn add(&mut self, baseline_balance_cents: i64, challenger_balance_cents: i64) {
+        self.differences_cents
+            .add((challenger_balance_cents - baseline_balance_cents) as f64);
+        self.baseline_balances_cents
+            .add(baseline_balance_cents as f64);
+        self.challenger_balances_cents
+            .add(challenger_balance_cents as f64);
+    }
+
+    /// `None` unless both agents played the same two games or more
+    fn to_comparison(&self, baseline: &str, challenger: &str) -> Option<AgentComparison> {
+        let number_of_games = self.differences_cents.get_count();
+        if number_of_games < 2 {
+            return None;
+        }
+        let mean_difference_cents = self.differences_cents.get_mean();
+        let paired_variance = self.differences_cents.get_variance();
+        let unpaired_variance = self.baseline_balances_cents.get_variance()
+            + self.challenger_balances_cents.get_variance();
+        let paired_standard_error_cents = (paired_variance / number_of_games as f64).sqrt();
+        let t_statistic = if paired_standard_error_cents > 0.0 {
+            mean_difference_cents / paired_standard_error_cents
+        } else if mean_difference_cents == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY.copysign(mean_difference_cents)
+        };
+        return Some(AgentComparison {
+            baseline: baseline.to_string(),
+            challenger: challenger.to_string(),
+            number_of_games,
+            mean_difference_cents,
+            paired_standard_error_cents,
+            unpaired_standard_error_cents: (unpaired_variance / number_of_games as f64).sqrt(),
+            variance_reduction: if unpaired_variance > 0.0 {
+                1.0 - paired_variance / unpaired_variance
+            } else {
+                0.0
+            },
+            t_statistic,
+            p_value: erfc(t_statistic.abs() / std::f64::consts::SQRT_2),
+        });
+    }
+}
+
+/// Compares every pair of agents game by game as games come in, without
+/// keeping their final balances around
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ComparisonTotals {
+    /// Agents in the order they first sat at a table
+    agent_names: Vec<String>,
+    /// By the indices of the baseline and challenger in `agent_names`
+    pair_totals: HashMap<(usize, usize), PairTotals>,
+}
+
+impl ComparisonTotals {
+    pub fn add_game(&mut self, game: &RouletteGame) {
+        let balances_cents: Vec<(usize, i64)> = game
+            .agents
+            .iter()
+            .map(|agent| (self.get_agent_index(&agent.name), agent.balance_cents))
+            .collect();
+        for ((baseline, baseline_balance_cents), (challenger, challenger_balance_cents)) in
+            balances_cents.iter().tuple_combinations()
+        {
+            let (pair, baseline_balance_cents, challenger_balance_cents) = if baseline < challenger
+            {
+                (
+                    (*baseline, *challenger),
+                    *baseline_balance_cents,
+                    *challenger_balance_cents,
+                )
+            } else {
+                (
+                    (*challenger, *baseline),
+                    *challenger_balance_cents,
+                    *baseline_balance_cents,
+                )
+            };
+            self.pair_totals
+                .entry(pair)
+                .or_default()
+                .add(baseline_balance_cents, challenger_balance_cents);
+        }
+    }
+
+    /// Every pair of agents, the later agent against the earlier one
+    pub fn to_agent_comparisons(&self) -> Vec<AgentComparison> {
+        return (0..self.agent_names.len())
+            .tuple_combinations()
+            .filter_map(|(baseline, challenger)| {
+                self.pair_totals
+                    .get(&(baseline, challenger))?
+                    .to_comparison(&self.agent_names[baseline], &self.agent_names[challenger])
+            })
+            .collect();
+    }
+
+    fn get_agent_index(&mut self, agent_name: &str) -> usize {
+        if let Some(index) = self.agent_names.iter().position(|name| name == agent_name) {
+            return index;
+        }
+        self.agent_names.push(agent_name.to_string());
+        return self.agent_names.len() - 1;
+    }
+}
+
+// Abramowitz and Stegun 7.1.26, off by less than 1.5e-7
+fn erfc(x: f64) -> f64 {
+    if x.is_infinite() {
+        return if x > 0.0 { 0.0 } else { 2.0 };
+    }
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let polynomial = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let result = polynomial * (-z * z).exp();
+    if x < 0.0 {
+        return 2.0 - result;
+    }
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(
+        baseline_balances_cents: &[i64],
+        challenger_balances_cents: &[i64],
+    ) -> Option<AgentComparison> {
+        let mut pair_totals = PairTotals::default();
+        for (baseline, challenger) in baseline_balances_cents
+            .iter()
+            .zip(challenger_balances_cents.iter())
+        {
+            pair_totals.add(*baseline, *challenger);
+        }
+        return pair_totals.to_comparison("A", "B");
+    }
+
+    #[test]
+    fn test_to_comparison() {
+        let comparison = compare(&[1000, 3000, 5000, 7000], &[1500, 3000, 5500, 8000]).unwrap();
+        assert_eq!(comparison.number_of_games, 4);
+        assert!((comparison.mean_difference_cents - 500.0).abs() < 1e-9);
+        // differences 500, 0, 500, 1000
+        assert!((comparison.paired_standard_error_cents - 204.124).abs() < 0.001);
+        assert!(comparison.unpaired_standard_error_cents > comparison.paired_standard_error_cents);
+        assert!(comparison.variance_reduction > 0.9);
+        assert!((comparison.t_statistic - 2.449).abs() < 0.001);
+        assert!((comparison.p_value - 0.0143).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_to_comparison_identical_agents() {
+        let comparison = compare(&[1000, 2000, 500], &[1000, 2000, 500]).unwrap();
+        assert_eq!(comparison.mean_difference_cents, 0.0);
+        assert_eq!(comparison.paired_standard_error_cents, 0.0);
+        assert_eq!(comparison.variance_reduction, 1.0);
+        assert_eq!(comparison.t_statistic, 0.0);
+        assert!((comparison.p_value - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_to_comparison_needs_two_games() {
+        assert!(compare(&[1000], &[2000]).is_none());
+    }
+
+    #[test]
+    fn test_erfc() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-6);
+        assert!((erfc(1.0) - 0.157299).abs() < 1e-6);
+        assert!((erfc(-1.0) - 1.842701).abs() < 1e-6);
+        assert_eq!(erfc(f64::INFINITY), 0.0);
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use super::distribution::DistributionTotals;
+
+/// Balance of an agent across all games at one round
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TrajectoryPoint {
+    pub round_number: i32,
+    pub mean: f64,
+    pub median: f64,
+    pub p5: f64,
+    pub p95: f64,
+}
+
+/// Percentile bands of an agent's balance over the rounds, the "fan chart" of a strategy
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct BalanceTrajectory {
+    pub points: Vec<TrajectoryPoint>,
+}
+
+impl BalanceTrajectory {
+    /// Keeps `number_of_points` evenly spaced points, always including the last round
+    pub fn downsample(&self, number_of_points: usize) -> BalanceTrajectory {
+        return BalanceTrajectory {
+            points: evenly_spaced(self.points.len(), number_of_points)
+                .into_iter()
+                .map(|index| self.points[index].clone())
+                .collect(),
+        };
+    }
+}
+
+/// Running balances of an agent at the sampled rounds, in the same bounded
+/// memory for any number of games
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TrajectoryTotals {
+    round_numbers: Vec<i32>,
+    /// Balances of the games, per sampled round
+    balances_cents: Vec<DistributionTotals>,
+}
+
+impl TrajectoryTotals {
+    /// Samples `number_of_points` of the `number_of_rounds` rounds of a game
+    pub fn new(number_of_rounds: i32, number_of_points: usize) -> Self {
+        let round_numbers: Vec<i32> =
+            evenly_spaced(number_of_rounds.max(0) as usize, number_of_points)
+                .into_iter()
+                .map(|index| index as i32 + 1)
+                .collect();
+        return TrajectoryTotals {
+            balances_cents: vec![DistributionTotals::default(); round_numbers.len()],
+            round_numbers,
+        };
+    }
+
+    /// `balance_at` gives the balance after a round number
+    pub fn add_session<F>(&mut self, balance_at: F)
+    where
+        F: Fn(i32) -> i64,
+    {
+        for (round_number, balances_cents) in self
+            .round_numbers
+            .iter()
+            .zip(self.balances_cents.iter_mut())
+        {
+            balances_cents.add(balance_at(*round_number));
+        }
+    }
+
+    pub fn to_balance_trajectory(&self) -> BalanceTrajectory {
+        return BalanceTrajectory {
+            points: self
+                .round_numbers
+                .iter()
+                .zip(self.balances_cents.iter())
+                .filter_map(|(round_number, balances_cents)| {
+                    let distribution = balances_cents.to_distribution()?;
+                    Some(TrajectoryPoint {
+                        round_number: *round_number,
+                        mean: distribution.mean,
+                        median: distribution.median,
+                        p5: distribution.p5,
+                        p95: distribution.p95,
+                    })
+                })
+                .collect(),
+        };
+    }
+}
+
+/// `number_of_points` evenly spaced indexes of `len` items, ending on the last one
+fn evenly_spaced(len: usize, number_of_points: usize) -> Vec<usize> {
+    if number_of_points >= len {
+        return (0..len).collect();
+    }
+    return (1..=number_of_points)
+        .map(|point| point * len / number_of_points - 1)
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evenly_spaced() {
+        assert_eq!(evenly_spaced(10, 5), vec![1, 3, 5, 7, 9]);
+        assert_eq!(evenly_spaced(10, 3), vec![2, 5, 9]);
+        assert_eq!(evenly_spaced(3, 5), vec![0, 1, 2]);
+        assert!(evenly_spaced(10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let mut trajectory_totals = TrajectoryTotals::new(100, 4);
+        for game in 0..5 {
+            trajectory_totals.add_session(|round_number| (round_number * game) as i64);
+        }
+        let trajectory = trajectory_totals.to_balance_trajectory();
+        let round_numbers: Vec<i32> = trajectory
+            .points
+            .iter()
+            .map(|point| point.round_number)
+            .collect();
+        assert_eq!(round_numbers, vec![25, 50, 75, 100]);
+        assert_eq!(trajectory.points[3].mean, 200.0);
+        assert_eq!(trajectory.points[3].median, 200.0);
+        assert_eq!(trajectory.points[3].p5, 20.0);
+        assert_eq!(trajectory.points[3].p95, 380.0);
+
+        let downsampled = trajectory.downsample(2);
+        assert_eq!(downsampled.points.len(), 2);
+        assert_eq!(downsampled.points[1].round_number, 100);
+    }
+
+    #[test]
+    fn test_trajectory_memory_is_bounded() {
+        let mut trajectory_totals = TrajectoryTotals::new(100, 4);
+        let retained_after = |trajectory_totals: &mut TrajectoryTotals, number_of_games: i64| {
+            for game in 0..number_of_games {
+                trajectory_totals.add_session(|round_number| round_number as i64 * game);
+            }
+            trajectory_totals
+                .balances_cents
+                .iter()
+                .map(|balances_cents| balances_cents.get_retained_values())
+                .sum::<usize>()
+        };
+        let retained = retained_after(&mut trajectory_totals, 10_000);
+        assert_eq!(retained_after(&mut trajectory_totals, 100_000), retained);
+        let trajectory = trajectory_totals.to_balance_trajectory();
+        assert_eq!(trajectory.points.len(), 4);
+        assert!(trajectory.points[3].p5 < trajectory.points[3].median);
+        assert!(trajectory.points[3].median < trajectory.points[3].p95);
+    }
+}
@@ -0,0 +1,375 @@
+use serde::{Deserialize, Serialize};
+
+/// z-score of a two-sided 95% confidence interval
+const Z_95: f64 = 1.96;
+/// Values a `DistributionTotals` keeps for exact percentiles, past this many
+/// the percentiles are estimated in constant memory
+const EXACT_VALUES: usize = 1000;
+/// Percentiles of a `Distribution`, in the order of its fields
+const FRACTIONS: [f64; 5] = [0.5, 0.05, 0.25, 0.75, 0.95];
+
+/// Spread of a set of values in cents, e.g. the final balances of an agent over all games
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: i64,
+    pub mean: f64,
+    pub min: i64,
+    pub max: i64,
+    pub median: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p95: f64,
+    /// Sample standard deviation
+    pub std_dev: f64,
+    /// 95% confidence interval of the mean, as (lower, upper)
+    pub mean_confidence_interval_95: (f64, f64),
+}
+
+impl Distribution {
+    pub fn from_values(values: &[i64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().map(|value| *value as f64).sum::<f64>() / count;
+        let std_dev = if sorted.len() > 1 {
+            let squared_deviations: f64 = sorted
+                .iter()
+                .map(|value| (*value as f64 - mean).powi(2))
+                .sum();
+            (squared_deviations / (count - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let margin = Z_95 * std_dev / count.sqrt();
+        return Some(Distribution {
+            count: sorted.len() as i64,
+            mean,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            median: Self::percentile(&sorted, 0.5),
+            p5: Self::percentile(&sorted, 0.05),
+            p25: Self::percentile(&sorted, 0.25),
+            p75: Self::percentile(&sorted, 0.75),
+            p95: Self::percentile(&sorted, 0.95),
+            std_dev,
+            mean_confidence_interval_95: (mean - margin, mean + margin),
+        });
+    }
+
+    /// Interpolates linearly between the two closest ranks of the sorted values
+    fn percentile(sorted: &[i64], fraction: f64) -> f64 {
+        let rank = fraction * (sorted.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f64;
+        return sorted[lower] as f64 + (sorted[upper] - sorted[lower]) as f64 * weight;
+    }
+}
+
+/// Running mean and variance, Welford's algorithm
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct RunningMoments {
+    count: i64,
+    mean: f64,
+    /// Sum of the squared deviations from the mean
+    squared_deviations: f64,
+}
+
+impl RunningMoments {
+    pub fn add(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.squared_deviations += delta * (value - self.mean);
+    }
+
+    pub fn get_count(&self) -> i64 {
+        return self.count;
+    }
+
+    pub fn get_mean(&self) -> f64 {
+        return self.mean;
+    }
+
+    /// Sample variance, 0 below two values
+    pub fn get_variance(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        return self.squared_deviations / (self.count - 1) as f64;
+    }
+}
+
+/// Streaming estimate of one quantile in five markers, the P² algorithm of
+/// Jain and Chlamtac
+#[derive(Debug, PartialEq, Clone)]
+struct QuantileEstimate {
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired_positions: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl QuantileEstimate {
+    /// Puts the markers on the exact ranks of `sorted`, as if its values had been streamed
+    fn from_sorted(sorted: &[i64], fraction: f64) -> Self {
+        let last_rank = (sorted.len() - 1) as f64;
+        let increments = [0.0, fraction / 2.0, fraction, (1.0 + fraction) / 2.0, 1.0];
+        let desired_positions = increments.map(|increment| 1.0 + last_rank * increment);
+        let positions = desired_positions.map(|position| position.round());
+        return QuantileEstimate {
+            heights: positions.map(|position| sorted[position as usize - 1] as f64),
+            positions,
+            desired_positions,
+            increments,
+        };
+    }
+
+    fn add(&mut self, value: f64) {
+        let cell = if value < self.heights[0] {
+            self.heights[0] = value;
+            0
+        } else if value >= self.heights[4] {
+            self.heights[4] = value;
+            3
+        } else {
+            (0..4)
+                .find(|index| value < self.heights[index + 1])
+                .unwrap_or(3)
+        };
+        for position in self.positions.iter_mut().skip(cell + 1) {
+            *position += 1.0;
+        }
+        for (desired_position, increment) in self
+            .desired_positions
+            .iter_mut()
+            .zip(self.increments.iter())
+        {
+            *desired_position += increment;
+        }
+        for index in 1..4 {
+            let offset = self.desired_positions[index] - self.positions[index];
+            if (offset >= 1.0 && self.positions[index + 1] - self.positions[index] > 1.0)
+                || (offset <= -1.0 && self.positions[index - 1] - self.positions[index] < -1.0)
+            {
+                let step = offset.signum();
+                let parabolic = self.parabolic(index, step);
+                self.heights[index] =
+                    if self.heights[index - 1] < parabolic && parabolic < self.heights[index + 1] {
+                        parabolic
+                    } else {
+                        self.linear(index, step)
+                    };
+                self.positions[index] += step;
+            }
+        }
+    }
+
+    fn parabolic(&self, index: usize, step: f64) -> f64 {
+        let (heights, positions) = (&self.heights, &self.positions);
+        return heights[index]
+            + step / (positions[index + 1] - positions[index - 1])
+                * ((positions[index] - positions[index - 1] + step)
+                    * (heights[index + 1] - heights[index])
+                    / (positions[index + 1] - positions[index])
+                    + (positions[index + 1] - positions[index] - step)
+                        * (heights[index] - heights[index - 1])
+                        / (positions[index] - positions[index - 1]));
+    }
+
+    fn linear(&self, index: usize, step: f64) -> f64 {
+        let neighbour = if step > 0.0 { index + 1 } else { index - 1 };
+        return self.heights[index]
+            + step * (self.heights[neighbour] - self.heights[index])
+                / (self.positions[neighbour] - self.positions[index]);
+    }
+
+    fn get_estimate(&self) -> f64 {
+        return self.heights[2];
+    }
+}
+
+/// Builds a `Distribution` one value at a time in bounded memory. The first
+/// `EXACT_VALUES` values are kept, which keeps the percentiles of small runs
+/// exact, after that they are estimated.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct DistributionTotals {
+    moments: RunningMoments,
+    min: i64,
+    max: i64,
+    values: Vec<i64>,
+    /// Estimates of `FRACTIONS`, only once there are more than `EXACT_VALUES`
+    quantile_estimates: Vec<QuantileEstimate>,
+}
+
+impl DistributionTotals {
+    pub fn add(&mut self, value: i64) {
+        if self.moments.get_count() == 0 {
+            self.min = value;
+            self.max = value;
+        }
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.moments.add(value as f64);
+        if !self.quantile_estimates.is_empty() {
+            for quantile_estimate in self.quantile_estimates.iter_mut() {
+                quantile_estimate.add(value as f64);
+            }
+            return;
+        }
+        self.values.push(value);
+        if self.values.len() > EXACT_VALUES {
+            let mut sorted = std::mem::take(&mut self.values);
+            sorted.sort_unstable();
+            self.quantile_estimates = FRACTIONS
+                .iter()
+                .map(|fraction| QuantileEstimate::from_sorted(&sorted, *fraction))
+                .collect();
+        }
+    }
+
+    pub fn to_distribution(&self) -> Option<Distribution> {
+        if self.quantile_estimates.is_empty() {
+            return Distribution::from_values(&self.values);
+        }
+        let count = self.moments.get_count() as f64;
+        let mean = self.moments.get_mean();
+        let std_dev = self.moments.get_variance().sqrt();
+        let margin = Z_95 * std_dev / count.sqrt();
+        let estimates: Vec<f64> = self
+            .quantile_estimates
+            .iter()
+            .map(|quantile_estimate| quantile_estimate.get_estimate())
+            .collect();
+        return Some(Distribution {
+            count: self.moments.get_count(),
+            mean,
+            min: self.min,
+            max: self.max,
+            median: estimates[0],
+            p5: estimates[1],
+            p25: estimates[2],
+            p75: estimates[3],
+            p95: estimates[4],
+            std_dev,
+            mean_confidence_interval_95: (mean - margin, mean + margin),
+        });
+    }
+
+    /// Values held in memory, which stops growing after `EXACT_VALUES`
+    #[cfg(test)]
+    pub fn get_retained_values(&self) -> usize {
+        return self.values.len()
+            + self.quantile_estimates.len() * std::mem::size_of::<QuantileEstimate>()
+                / std::mem::size_of::<i64>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_values() {
+        let distribution = Distribution::from_values(&[500, 100, 400, 200, 300]).unwrap();
+        assert_eq!(distribution.count, 5);
+        assert_eq!(distribution.mean, 300.0);
+        assert_eq!(distribution.min, 100);
+        assert_eq!(distribution.max, 500);
+        assert_eq!(distribution.median, 300.0);
+        assert_eq!(distribution.p5, 120.0);
+        assert_eq!(distribution.p25, 200.0);
+        assert_eq!(distribution.p75, 400.0);
+        assert_eq!(distribution.p95, 480.0);
+        assert_eq!(distribution.std_dev, 25000.0_f64.sqrt());
+        let margin = 1.96 * 25000.0_f64.sqrt() / 5.0_f64.sqrt();
+        assert_eq!(
+            distribution.mean_confidence_interval_95,
+            (300.0 - margin, 300.0 + margin)
+        );
+    }
+
+    #[test]
+    fn test_distribution_totals_exact() {
+        let mut distribution_totals = DistributionTotals::default();
+        for value in [500, 100, 400, 200, 300] {
+            distribution_totals.add(value);
+        }
+        assert_eq!(
+            distribution_totals.to_distribution(),
+            Distribution::from_values(&[500, 100, 400, 200, 300])
+        );
+        assert!(DistributionTotals::default().to_distribution().is_none());
+    }
+
+    #[test]
+    fn test_distribution_totals_estimated() {
+        // a deterministic shuffle of 0..100_000
+        let values: Vec<i64> = (0..100_000).map(|index| index * 7919 % 100_000).collect();
+        let mut distribution_totals = DistributionTotals::default();
+        for value in values.iter() {
+            distribution_totals.add(*value);
+        }
+        let exact = Distribution::from_values(&values).unwrap();
+        let estimated = distribution_totals.to_distribution().unwrap();
+        assert_eq!(estimated.count, exact.count);
+        assert_eq!((estimated.min, estimated.max), (exact.min, exact.max));
+        assert!((estimated.mean - exact.mean).abs() < 1e-6);
+        assert!((estimated.std_dev - exact.std_dev).abs() < 1e-6);
+        for (estimate, exact) in [
+            (estimated.median, exact.median),
+            (estimated.p5, exact.p5),
+            (estimated.p25, exact.p25),
+            (estimated.p75, exact.p75),
+            (estimated.p95, exact.p95),
+        ] {
+            // within half a percent of the range
+            assert!(
+                (estimate - exact).abs() < 500.0,
+                "{} vs {}",
+                estimate,
+                exact
+            );
+        }
+    }
+
+    #[test]
+    fn test_distribution_totals_memory_is_bounded() {
+        let mut distribution_totals = DistributionTotals::default();
+        let retained_after = |distribution_totals: &mut DistributionTotals, count: i64| {
+            for value in 0..count {
+                distribution_totals.add(value);
+            }
+            distribution_totals.get_retained_values()
+        };
+        let retained = retained_after(&mut distribution_totals, 10 * EXACT_VALUES as i64);
+        assert_eq!(
+            retained_after(&mut distribution_totals, 100 * EXACT_VALUES as i64),
+            retained
+        );
+    }
+
+    #[test]
+    fn test_running_moments() {
+        let mut running_moments = RunningMoments::default();
+        for value in [500.0, 100.0, 400.0, 200.0, 300.0] {
+            running_moments.add(value);
+        }
+        assert_eq!(running_moments.get_count(), 5);
+        assert_eq!(running_moments.get_mean(), 300.0);
+        assert_eq!(running_moments.get_variance(), 25000.0);
+    }
+
+    #[test]
+    fn test_from_single_value() {
+        let distribution = Distribution::from_values(&[-700]).unwrap();
+        assert_eq!(distribution.median, -700.0);
+        assert_eq!(distribution.std_dev, 0.0);
+        assert_eq!(distribution.mean_confidence_interval_95, (-700.0, -700.0));
+        assert!(Distribution::from_values(&[]).is_none());
+    }
+}
@@ -0,0 +1,31 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// What happens to even-money bets (`Color`, `EvenOdd`, `Half`) when a zero hits.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum EvenMoneyRule {
+    /// The bet is lost
+    #[default]
+    Standard,
+    /// French rule: half the stake is returned
+    LaPartage,
+    /// French rule: the bet is locked for the next spin and returned if it wins
+    EnPrison,
+    /// American rule: half the stake is returned on 0 and 00
+    Surrender,
+}
+
+impl fmt::Display for EvenMoneyRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                EvenMoneyRule::Standard => "Standard",
+                EvenMoneyRule::LaPartage => "La Partage",
+                EvenMoneyRule::EnPrison => "En Prison",
+                EvenMoneyRule::Surrender => "Surrender",
+            }
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+
+use super::{even_money_rule::EvenMoneyRule, payout_table::PayoutTable};
+use crate::bet::{bet::Bet, bet_state::BetState, bet_value::BetValue};
+use crate::board::board::Board;
+use crate::strategy::strategy::Strategy;
+use crate::types::color::Color;
+
+/// Exact odds of a bet on a board, worked out from the payout table the games
+/// settle bets with. Every slot is taken to be equally likely.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct BetExpectation {
+    pub win_probability: f64,
+    /// Returned per unit staked on the best win, stake included
+    pub payout_multiplier: f64,
+    /// Net result per unit staked, the house edge is its negative
+    pub expected_value: f64,
+    /// Variance of the net result per unit staked
+    pub variance: f64,
+}
+
+impl BetExpectation {
+    pub fn calculate(
+        board: &Board,
+        bet_value: &BetValue,
+        even_money_rule: EvenMoneyRule,
+        payout_table: &PayoutTable,
+    ) -> Self {
+        let number_of_slots = board.slots.len() as f64;
+        let multipliers: Vec<f64> = board
+            .slots
+            .iter()
+            .map(|slot| payout_table.get_payout_multiplier(bet_value, slot, &board.wheel))
+            .collect();
+        let win_probability = multipliers
+            .iter()
+            .filter(|multiplier| **multiplier > 0.0)
+            .count() as f64
+            / number_of_slots;
+
+        // mean of the return and of its square per unit staked, stake included
+        let mut return_sum = 0.0;
+        let mut squared_return_sum = 0.0;
+        for (slot, multiplier) in board.slots.iter().zip(multipliers.iter()) {
+            let (slot_return, slot_squared_return) = if *multiplier > 0.0 {
+                (*multiplier, *multiplier * *multiplier)
+            } else if slot.color == Color::Green && bet_value.is_even_money() {
+                match even_money_rule {
+                    EvenMoneyRule::Standard => (0.0, 0.0),
+                    EvenMoneyRule::LaPartage | EvenMoneyRule::Surrender => (0.5, 0.25),
+                    // the stake comes back if the next spin wins
+                    EvenMoneyRule::EnPrison => (win_probability, win_probability),
+                }
+            } else {
+                (0.0, 0.0)
+            };
+            return_sum += slot_return;
+            squared_return_sum += slot_squared_return;
+        }
+        let mean_return = return_sum / number_of_slots;
+        return BetExpectation {
+            win_probability,
+            payout_multiplier: multipliers.into_iter().fold(0.0, f64::max),
+            expected_value: mean_return - 1.0,
+            variance: squared_return_sum / number_of_slots - mean_return * mean_return,
+        };
+    }
+
+    pub fn get_house_edge(&self) -> f64 {
+        return -self.expected_value;
+    }
+}
+
+/// What a full set of bets is expected to cost per spin at their opening stakes
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct AgentExpectation {
+    pub staked_cents_per_spin: i64,
+    pub expected_loss_cents_per_spin: f64,
+}
+
+impl AgentExpectation {
+    /// Bets retired without ever being staked are left out
+    pub fn calculate(
+        board: &Board,
+        bets: &[Bet],
+        even_money_rule: EvenMoneyRule,
+        payout_table: &PayoutTable,
+    ) -> Self {
+        let mut staked_cents_per_spin = 0;
+        let mut expected_loss_cents_per_spin = 0.0;
+        for bet in bets.iter().filter(|bet| {
+            bet.bet_state != BetState::Retired
+                || bet
+                    .bet_logs
+                    .iter()
+                    .any(|bet_log| bet_log.bet_state != BetState::Retired)
+        }) {
+            let amount_cents = bet.strategy.opening_amount_cents(bet.initial_amount_cents);
+            let bet_expectation =
+                BetExpectation::calculate(board, &bet.bet_value, even_money_rule, payout_table);
+            staked_cents_per_spin += amount_cents;
+            expected_loss_cents_per_spin += amount_cents as f64 * bet_expectation.get_house_edge();
+        }
+        return AgentExpectation {
+            staked_cents_per_spin,
+            expected_loss_cents_per_spin,
+        };
+    }
+
+    /// Expected loss per unit staked over all the bets
+    pub fn get_house_edge(&self) -> f64 {
+        if self.staked_cents_per_spin > 0 {
+            return self.expected_loss_cents_per_spin / self.staked_cents_per_spin as f64;
+        }
+        return 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roulette::roulette_type::RouletteType;
+    use crate::strategy::{labouchere::Labouchere, strategy_type::StrategyType};
+    use crate::types::{
+        adjacent_numbers::AdjacentNumbers, call_bet::CallBet, dozen::Dozen, even_odd::EvenOdd,
+    };
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-12,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_straight_up() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let expectation = BetExpectation::calculate(
+            &board,
+            &BetValue::Number(17),
+            EvenMoneyRule::Standard,
+            &PayoutTable::default(),
+        );
+        assert_close(expectation.win_probability, 1.0 / 37.0);
+        assert_eq!(expectation.payout_multiplier, 36.0);
+        assert_close(expectation.get_house_edge(), 1.0 / 37.0);
+        assert_close(
+            expectation.variance,
+            36.0 * 36.0 / 37.0 - (36.0_f64 / 37.0).powi(2),
+        );
+
+        let board = Board::generate(&RouletteType::American).unwrap();
+        let expectation = BetExpectation::calculate(
+            &board,
+            &BetValue::Number(-1),
+            EvenMoneyRule::Standard,
+            &PayoutTable::default(),
+        );
+        assert_close(expectation.get_house_edge(), 2.0 / 38.0);
+    }
+
+    #[test]
+    fn test_five_number() {
+        let board = Board::generate(&RouletteType::American).unwrap();
+        let expectation = BetExpectation::calculate(
+            &board,
+            &BetValue::AdjacentNumbers(AdjacentNumbers {
+                numbers: vec![-1, 0, 1, 2, 3],
+            }),
+            EvenMoneyRule::Standard,
+            &PayoutTable::american(),
+        );
+        assert_eq!(expectation.payout_multiplier, 7.0);
+        assert_close(expectation.get_house_edge(), 3.0 / 38.0);
+    }
+
+    #[test]
+    fn test_call_bets() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let expectation = BetExpectation::calculate(
+            &board,
+            &BetValue::CallBet(CallBet::VoisinsDuZero),
+            EvenMoneyRule::Standard,
+            &PayoutTable::default(),
+        );
+        assert_close(expectation.win_probability, 17.0 / 37.0);
+        assert_close(expectation.get_house_edge(), 1.0 / 37.0);
+    }
+
+    #[test]
+    fn test_even_money_rules() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let bet_value = BetValue::EvenOdd(EvenOdd::Even);
+        let standard = BetExpectation::calculate(
+            &board,
+            &bet_value,
+            EvenMoneyRule::Standard,
+            &PayoutTable::default(),
+        );
+        assert_close(standard.win_probability, 18.0 / 37.0);
+        assert_close(standard.get_house_edge(), 1.0 / 37.0);
+        let la_partage = BetExpectation::calculate(
+            &board,
+            &bet_value,
+            EvenMoneyRule::LaPartage,
+            &PayoutTable::default(),
+        );
+        assert_close(la_partage.get_house_edge(), 0.5 / 37.0);
+        let en_prison = BetExpectation::calculate(
+            &board,
+            &bet_value,
+            EvenMoneyRule::EnPrison,
+            &PayoutTable::default(),
+        );
+        assert_close(en_prison.get_house_edge(), (1.0 - 18.0 / 37.0) / 37.0);
+    }
+
+    #[test]
+    fn test_agent_expectation() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let bets = vec![
+            Bet {
+                amount_cents: 1000,
+                bet_logs: vec![],
+                bet_state: BetState::Active,
+                bet_value: BetValue::Dozen(Dozen::One),
+                initial_amount_cents: 1000,
+                payout_cents: 0,
+                progression_factor: 2,
+                strategy: StrategyType::default(),
+                trigger: None,
+                table_limit_hit: false,
+            },
+            Bet {
+                amount_cents: 4000,
+                bet_logs: vec![],
+                bet_state: BetState::Lost,
+                bet_value: BetValue::Number(0),
+                initial_amount_cents: 100,
+                payout_cents: 0,
+                progression_factor: 2,
+                strategy: StrategyType::Labouchere(Labouchere::new(vec![1, 2, 3])),
+                trigger: None,
+                table_limit_hit: false,
+            },
+        ];
+        let expectation = AgentExpectation::calculate(
+            &board,
+            &bets,
+            EvenMoneyRule::Standard,
+            &PayoutTable::default(),
+        );
+        assert_eq!(expectation.staked_cents_per_spin, 1400);
+        assert_close(expectation.expected_loss_cents_per_spin, 1400.0 / 37.0);
+        assert_close(expectation.get_house_edge(), 1.0 / 37.0);
+    }
+}
@@ -1,6 +1,15 @@
 use serde::{Deserialize, Serialize};
 
-use super::roulette_type::RouletteType;
+use crate::bet::naming_scheme::NamingScheme;
+use crate::error::Error;
+
+use super::{
+    even_money_rule::EvenMoneyRule, log_retention::LogRetention, payout_table::PayoutTable,
+    roulette_type::RouletteType, table_limits::TableLimits,
+};
+
+/// Rounds sampled for the balance trajectories of a run that doesn't set it
+pub const DEFAULT_TRAJECTORY_POINTS: usize = 100;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct GameConfig {
@@ -8,4 +17,98 @@ pub struct GameConfig {
     pub number_of_games: i32,
     pub allow_negative_balance: bool,
     pub roulette_type: Option<RouletteType>,
+    /// Runs the same agents on every listed wheel type instead of only on `roulette_type`
+    pub roulette_types: Option<Vec<RouletteType>>,
+    pub seed: Option<u64>,
+    pub even_money_rule: Option<EvenMoneyRule>,
+    /// Runs the same agents under every listed rule instead of only under `even_money_rule`
+    pub even_money_rules: Option<Vec<EvenMoneyRule>>,
+    pub table_limits: Option<TableLimits>,
+    /// Payouts of every bet type, the default table of each wheel type when left out
+    pub payout_table: Option<PayoutTable>,
+    /// What is kept of finished games besides the stats, `None` when left out
+    pub log_retention: Option<LogRetention>,
+    /// Rounds sampled for the balance trajectories, `DEFAULT_TRAJECTORY_POINTS` when left out
+    pub trajectory_points: Option<usize>,
+    /// Names of the bet types in the stats, `Classic` when left out
+    pub naming_scheme: Option<NamingScheme>,
+    /// Plays every agent on its own copy of each game's spins and compares
+    /// them pairwise, off when left out
+    pub common_random_numbers: Option<bool>,
+}
+
+impl GameConfig {
+    /// Rejects runs without games or rounds, and wheel types and even-money rules
+    /// that are played together but don't go together, every listed wheel type
+    /// being played under every rule
+    pub fn validate(&self) -> Result<(), Error> {
+        for (name, value) in [
+            ("number_of_games", self.number_of_games),
+            ("number_of_rounds", self.number_of_rounds),
+        ] {
+            if value <= 0 {
+                return Err(Error::GenericError {
+                    message: format!("{} must be positive, got {}", name, value),
+                    nested_error: None,
+                });
+            }
+        }
+        let roulette_types = self.get_roulette_types();
+        if let Some(roulette_type) = roulette_types
+            .iter()
+            .find(|roulette_type| **roulette_type != RouletteType::American)
+        {
+            if self
+                .get_even_money_rules()
+                .contains(&EvenMoneyRule::Surrender)
+            {
+                return Err(Error::GenericError {
+                    message: format!(
+                        "The {} rule is only played on the American wheel, not on the {} wheel",
+                        EvenMoneyRule::Surrender,
+                        roulette_type
+                    ),
+                    nested_error: None,
+                });
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn get_roulette_types(&self) -> Vec<RouletteType> {
+        return match &self.roulette_types {
+            Some(roulette_types) if !roulette_types.is_empty() => roulette_types.clone(),
+            _ => vec![self.roulette_type.unwrap_or(RouletteType::European)],
+        };
+    }
+
+    pub fn get_even_money_rules(&self) -> Vec<EvenMoneyRule> {
+        return match &self.even_money_rules {
+            Some(even_money_rules) if !even_money_rules.is_empty() => even_money_rules.clone(),
+            _ => vec![self.even_money_rule.unwrap_or_default()],
+        };
+    }
+
+    pub fn get_log_retention(&self) -> LogRetention {
+        return self.log_retention.unwrap_or_default();
+    }
+
+    pub fn get_naming_scheme(&self) -> NamingScheme {
+        return self.naming_scheme.unwrap_or_default();
+    }
+
+    pub fn uses_common_random_numbers(&self) -> bool {
+        return self.common_random_numbers.unwrap_or(false);
+    }
+
+    pub fn get_trajectory_points(&self) -> usize {
+        return self.trajectory_points.unwrap_or(DEFAULT_TRAJECTORY_POINTS);
+    }
+
+    /// Games played by a run, `number_of_games` for every wheel type and even-money rule
+    pub fn get_total_number_of_games(&self) -> i32 {
+        return self.number_of_games
+            * self.get_roulette_types().len() as i32
+            * self.get_even_money_rules().len() as i32;
+    }
 }
@@ -1,66 +1,447 @@
+use std::sync::OnceLock;
 use std::time::Instant;
 
+use itertools::{iproduct, Itertools};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use tracing::{error, info};
+
 use crate::agent::agent::Agent;
 use crate::error::Error;
-use crate::roulette::{game_configs::GameConfig, roulette_game::RouletteGame};
-use tracing::{error, info};
+use crate::roulette::{
+    even_money_rule::EvenMoneyRule,
+    game_configs::GameConfig,
+    log_retention::LogRetention,
+    roulette_game::RouletteGame,
+    roulette_type::RouletteType,
+    round_observer::BroadcastObserver,
+    run_controls::RunControls,
+    run_result::RunResult,
+    spin_source::{FixedSpinSource, RandomSpinSource, SpinSource},
+    stats_accumulator::StatsAccumulator,
+};
 
-pub async fn run(game_config: GameConfig, agents: Vec<Agent>) -> Result<Vec<RouletteGame>, Error> {
-    let start = Instant::now();
-    let mut results: Vec<RouletteGame> = Vec::new();
-    run_games_async(game_config.clone(), agents.clone())
+/// Games handed to the worker pool at once, per worker. Finished games of a
+/// chunk are folded into the stats before the next chunk starts, which keeps
+/// the memory of a run independent of its size.
+const GAMES_PER_WORKER: usize = 4;
+
+static WORKER_POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+pub async fn run(game_config: GameConfig, agents: Vec<Agent>) -> Result<RunResult, Error> {
+    return run_with_controls(game_config, agents, RunControls::default()).await;
+}
+
+/// Same as `run`, but publishes rounds to and can be cancelled through `controls`.
+/// A cancelled run fails as a whole.
+pub async fn run_with_controls(
+    game_config: GameConfig,
+    agents: Vec<Agent>,
+    controls: RunControls,
+) -> Result<RunResult, Error> {
+    // the games are played on the worker pool, the async runtime only waits
+    return tokio::task::spawn_blocking(move || run_games(&game_config, &agents, &controls))
         .await
-        .unwrap_or_else(|error| {
-            panic!(
-                "Failed to run games:\n{:?}\n{:?}\n{}",
-                game_config, agents, error
-            )
-        })
-        .iter()
-        .for_each(|result| match result {
-            Ok(r) => results.push(r.clone()),
-            Err(e) => error!("Failed to run game: {:?}", e),
+        .map_err(|e| Error::JoinError {
+            message: format!("Failed to join thread:\n {:?}", e),
+            nested_error: Some(Box::new(e)),
+        })?;
+}
+
+/// Threads the games are played on, one per core and apart from the async
+/// runtime so the server stays responsive during big runs
+fn worker_pool() -> &'static ThreadPool {
+    return WORKER_POOL.get_or_init(|| {
+        ThreadPoolBuilder::new()
+            .thread_name(|index| format!("simulation-worker-{}", index))
+            .build()
+            .expect("Unable to build the simulation worker pool")
+    });
+}
+
+fn run_games(
+    game_config: &GameConfig,
+    agents: &[Agent],
+    controls: &RunControls,
+) -> Result<RunResult, Error> {
+    let start = Instant::now();
+    let worker_pool = worker_pool();
+    let log_retention = game_config.get_log_retention();
+    let mut stats_accumulator = StatsAccumulator::new(
+        game_config.get_trajectory_points(),
+        game_config.uses_common_random_numbers(),
+    );
+    let mut games: Vec<RouletteGame> = Vec::new();
+    let mut failed_games = 0;
+    let mut spins: i64 = 0;
+
+    let game_keys = iproduct!(
+        game_config.get_roulette_types(),
+        game_config.get_even_money_rules(),
+        1..=game_config.number_of_games
+    );
+    for chunk in &game_keys.chunks(worker_pool.current_num_threads() * GAMES_PER_WORKER) {
+        if controls.is_cancelled() {
+            break;
+        }
+        let chunk: Vec<(RouletteType, EvenMoneyRule, i32)> = chunk.collect();
+        // results come back in the order of the chunk, so the stats don't
+        // depend on which game happens to finish first
+        let results: Vec<Result<(RouletteGame, i64), Error>> = worker_pool.install(|| {
+            chunk
+                .into_par_iter()
+                .map(|(roulette_type, even_money_rule, game_number)| {
+                    play_game(
+                        game_config,
+                        agents,
+                        controls,
+                        roulette_type,
+                        even_money_rule,
+                        game_number,
+                    )
+                })
+                .collect()
         });
+        for result in results {
+            match result {
+                Ok((mut game, game_spins)) => {
+                    spins += game_spins;
+                    stats_accumulator.add_game(&game);
+                    match log_retention {
+                        LogRetention::None => {}
+                        LogRetention::Summary => {
+                            game.drop_round_logs();
+                            games.push(game);
+                        }
+                        LogRetention::Full => games.push(game),
+                    }
+                }
+                // games skipped by a cancel aren't failures, the run is reported once below
+                Err(_) if controls.is_cancelled() => {}
+                Err(e) => {
+                    error!("Failed to run game: {:?}", e);
+                    failed_games += 1;
+                }
+            }
+        }
+    }
+
+    if controls.is_cancelled() {
+        info!("Simulation was cancelled after {} spins", spins);
+        return Err(Error::GenericError {
+            message: "Simulation was cancelled".to_string(),
+            nested_error: None,
+        });
+    }
+    let mut stats = stats_accumulator.to_stats();
+    stats.set_naming_scheme(game_config.get_naming_scheme());
+    let run_result = RunResult {
+        stats,
+        games,
+        failed_games,
+        spins,
+        elapsed: start.elapsed(),
+    };
+    info!("{} games failed to run", failed_games);
+    info!("---------------------------");
+    info!("Time elapsed: {:?}", run_result.elapsed);
     info!(
-        "{} games failed to run",
-        game_config.number_of_games - results.len() as i32
+        "Spins: {}, {:.0} spins/s on {} workers",
+        spins,
+        run_result.get_spins_per_second(),
+        worker_pool.current_num_threads()
     );
-    let duration = start.elapsed();
-    info!("---------------------------");
-    info!("Time elapsed: {:?}", duration);
     info!("---------------------------");
-    return Ok(results);
+    return Ok(run_result);
 }
 
-async fn run_games_async(
-    game_config: GameConfig,
-    agents: Vec<Agent>,
-) -> Result<Vec<Result<RouletteGame, Error>>, Error> {
-    let mut handles = Vec::new();
-
-    for game_number in 1..=game_config.number_of_games {
-        let game_agents = agents.clone();
-        let number_of_rounds = game_config.number_of_rounds;
-        let allow_negative_balance = game_config.allow_negative_balance;
-
-        let handle = tokio::spawn(async move {
-            let mut game: RouletteGame = RouletteGame::new(
-                game_number,
-                game_agents,
-                number_of_rounds,
-                allow_negative_balance,
-                None,
-            )?;
-            game.play()?;
-            return Ok(game);
+/// Plays one game and counts the spins of every table it took
+fn play_game(
+    game_config: &GameConfig,
+    agents: &[Agent],
+    controls: &RunControls,
+    roulette_type: RouletteType,
+    even_money_rule: EvenMoneyRule,
+    game_number: i32,
+) -> Result<(RouletteGame, i64), Error> {
+    if controls.is_cancelled() {
+        return Err(Error::GenericError {
+            message: format!("Game {} was cancelled", game_number),
+            nested_error: None,
         });
-        handles.push(handle);
     }
-    let results = futures::future::try_join_all(handles)
-        .await
-        .map_err(|e| Error::JoinError {
-            message: format!("Failed to join thread:\n {:?}", e),
-            nested_error: Some(Box::new(e)),
+    let mut spin_source = RandomSpinSource::for_game(game_config.seed, game_number);
+    let mut seated_agents = agents.to_vec();
+    if !game_config.uses_common_random_numbers() || seated_agents.len() < 2 {
+        let game = play_table(
+            game_config,
+            seated_agents,
+            controls,
+            roulette_type,
+            even_money_rule,
+            game_number,
+            &mut spin_source,
+        )?;
+        let spins = game.game_logs.len() as i64;
+        return Ok((game, spins));
+    }
+    // every agent gets a table of its own, all replaying the spins of the first table
+    let other_agents = seated_agents.split_off(1);
+    let mut game = play_table(
+        game_config,
+        seated_agents,
+        controls,
+        roulette_type,
+        even_money_rule,
+        game_number,
+        &mut spin_source,
+    )?;
+    let mut spins = game.game_logs.len() as i64;
+    for agent in other_agents {
+        let table = play_table(
+            game_config,
+            vec![agent],
+            controls,
+            roulette_type,
+            even_money_rule,
+            game_number,
+            &mut FixedSpinSource::from_game_logs(&game.game_logs),
+        )?;
+        spins += table.game_logs.len() as i64;
+        game.agents.extend(table.agents);
+    }
+    return Ok((game, spins));
+}
+
+fn play_table(
+    game_config: &GameConfig,
+    agents: Vec<Agent>,
+    controls: &RunControls,
+    roulette_type: RouletteType,
+    even_money_rule: EvenMoneyRule,
+    game_number: i32,
+    spin_source: &mut dyn SpinSource,
+) -> Result<RouletteGame, Error> {
+    let mut game: RouletteGame = RouletteGame::new(
+        game_number,
+        agents,
+        game_config.number_of_rounds,
+        game_config.allow_negative_balance,
+        Some(roulette_type),
+        Some(even_money_rule),
+        game_config.table_limits.clone(),
+        game_config.payout_table.clone(),
+    )?;
+    match &controls.round_events {
+        Some(sender) => {
+            game.play_observed(spin_source, &mut BroadcastObserver::new(sender.clone()))?
+        }
+        None => game.play(spin_source)?,
+    }
+    return Ok(game);
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::json::{json_reader::JsonReader, load_options::LoadOptions};
+    use crate::roulette::{
+        even_money_rule::EvenMoneyRule, roulette_type::RouletteType, stats::Stats,
+    };
+
+    #[tokio::test]
+    async fn test_run_seeded_is_reproducible() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 20;
+        game_config.seed = Some(1234);
+        game_config.log_retention = Some(LogRetention::Full);
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+
+        let first = run(game_config.clone(), agents.clone())
+            .await
+            .unwrap()
+            .games;
+        let second = run(game_config, agents).await.unwrap().games;
+        let first_logs: Vec<_> = first.iter().map(|game| game.game_logs.clone()).collect();
+        let second_logs: Vec<_> = second.iter().map(|game| game.game_logs.clone()).collect();
+        assert_eq!(first_logs, second_logs);
+        assert_eq!(Stats::from_games(&first), Stats::from_games(&second));
+    }
+
+    #[tokio::test]
+    async fn test_run_honors_roulette_type() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 3;
+        game_config.roulette_type = Some(RouletteType::American);
+        game_config.log_retention = Some(LogRetention::Summary);
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+
+        let games = run(game_config, agents).await.unwrap().games;
+        assert_eq!(games.len(), 3);
+        assert!(games.iter().all(|game| {
+            game.roulette_type == RouletteType::American && game.board.slots.len() == 38
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_run_mixed_roulette_types() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 4;
+        game_config.roulette_types = Some(vec![RouletteType::European, RouletteType::American]);
+        game_config.log_retention = Some(LogRetention::Summary);
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+
+        let games = run(game_config, agents).await.unwrap().games;
+        assert_eq!(games.len(), 8);
+        for roulette_type in [RouletteType::European, RouletteType::American] {
+            assert_eq!(
+                games
+                    .iter()
+                    .filter(|game| game.roulette_type == roulette_type)
+                    .count(),
+                4
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_even_money_rules() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 2;
+        game_config.even_money_rules = Some(vec![
+            EvenMoneyRule::Standard,
+            EvenMoneyRule::LaPartage,
+            EvenMoneyRule::EnPrison,
+        ]);
+        game_config.log_retention = Some(LogRetention::Summary);
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+
+        let games = run(game_config, agents).await.unwrap().games;
+        assert_eq!(games.len(), 6);
+        assert_eq!(
+            games
+                .iter()
+                .filter(|game| game.even_money_rule == EvenMoneyRule::EnPrison)
+                .count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_log_retention() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 40;
+        game_config.seed = Some(99);
+        game_config.roulette_types = Some(vec![RouletteType::European, RouletteType::American]);
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+
+        game_config.log_retention = Some(LogRetention::Full);
+        let full = run(game_config.clone(), agents.clone()).await.unwrap();
+        assert_eq!(full.games.len(), 80);
+        assert_eq!(full.failed_games, 0);
+        assert_eq!(full.spins, 80 * game_config.number_of_rounds as i64);
+        assert_eq!(full.stats, Stats::from_games(&full.games));
+
+        game_config.log_retention = Some(LogRetention::Summary);
+        let summary = run(game_config.clone(), agents.clone()).await.unwrap();
+        assert_eq!(summary.games.len(), 80);
+        assert!(summary.games.iter().all(|game| game.game_logs.is_empty()
+            && game.agents[0].agent_logs.is_empty()
+            && game.agents[0].strategic_bets[0].bet_logs.is_empty()));
+        assert_eq!(summary.games[5].agents, {
+            let mut game = full.games[5].clone();
+            game.drop_round_logs();
+            game.agents
         });
-    return results;
+
+        game_config.log_retention = Some(LogRetention::None);
+        let none = run(game_config, agents).await.unwrap();
+        assert!(none.games.is_empty());
+        assert_eq!(none.stats, full.stats);
+    }
+
+    #[tokio::test]
+    async fn test_run_common_random_numbers() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 20;
+        game_config.seed = Some(7);
+        game_config.common_random_numbers = Some(true);
+        game_config.log_retention = Some(LogRetention::Full);
+        let mut agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let mut twin = agents[0].clone();
+        twin.name = format!("{} Twin", twin.name);
+        agents.push(twin);
+
+        let run_result = run(game_config.clone(), agents.clone()).await.unwrap();
+        assert_eq!(run_result.games.len(), 20);
+        for game in run_result.games.iter() {
+            assert_eq!(game.agents.len(), agents.len());
+            assert_eq!(game.game_logs.len(), game_config.number_of_rounds as usize);
+            assert_eq!(
+                game.agents[0].agent_logs,
+                game.agents[agents.len() - 1].agent_logs
+            );
+        }
+        let comparisons = &run_result.stats.agent_comparisons;
+        assert_eq!(comparisons.len(), agents.len() * (agents.len() - 1) / 2);
+        let twin_comparison = comparisons
+            .iter()
+            .find(|comparison| comparison.challenger == agents[agents.len() - 1].name)
+            .filter(|comparison| comparison.baseline == agents[0].name)
+            .unwrap();
+        assert_eq!(twin_comparison.mean_difference_cents, 0.0);
+        assert_eq!(twin_comparison.paired_standard_error_cents, 0.0);
+
+        game_config.common_random_numbers = None;
+        let independent = run(game_config, agents).await.unwrap();
+        assert!(independent.stats.agent_comparisons.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_common_random_numbers_pairing() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 200;
+        game_config.number_of_rounds = 100;
+        game_config.seed = Some(11);
+        game_config.common_random_numbers = Some(true);
+        // flat bets, so no agent leaves a table early
+        let red =
+            json!({"bet_value": {"Color": "Red"}, "amount_cents": 100, "progression_factor": 1});
+        let dozen =
+            json!({"bet_value": {"Dozen": 1}, "amount_cents": 100, "progression_factor": 1});
+        let agents = JsonReader::parse_agents_with(
+            json!([
+                {"name": "Red", "balance_cents": 1000000, "strategic_bets": [red]},
+                {"name": "Red and Dozen", "balance_cents": 1000000, "strategic_bets": [red, dozen]}
+            ]),
+            &LoadOptions::for_game(&game_config, true),
+        )
+        .unwrap();
+
+        let run_result = run(game_config.clone(), agents).await.unwrap();
+        // every agent played every round at a table of its own
+        assert_eq!(run_result.spins, 2 * 200 * 100);
+        let comparison = &run_result.stats.agent_comparisons[0];
+        assert_eq!(comparison.number_of_games, 200);
+        assert!(
+            comparison.paired_standard_error_cents < comparison.unpaired_standard_error_cents,
+            "{:?}",
+            comparison
+        );
+        assert!(comparison.variance_reduction > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_run_cancelled() {
+        let game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let controls = RunControls::default();
+        controls.cancelled.store(true, Ordering::SeqCst);
+        let result = run_with_controls(game_config, agents, controls).await;
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,28 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// What happens to a progression whose next stake is above the table maximum.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum LimitPolicy {
+    /// The stake is lowered to the table maximum
+    #[default]
+    Clamp,
+    /// The progression starts over from the base stake
+    Reset,
+    /// The bet is taken off the table for the rest of the game
+    Stop,
+}
+
+impl fmt::Display for LimitPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                LimitPolicy::Clamp => "Clamp",
+                LimitPolicy::Reset => "Reset",
+                LimitPolicy::Stop => "Stop",
+            }
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// How much of every finished game a run keeps once its stats have been taken.
+/// The stats themselves take the same memory for any number of games, what is
+/// kept here grows with every game.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum LogRetention {
+    /// Only the stats, games are dropped as soon as they finish
+    #[default]
+    None,
+    /// Final balances, bets and exits of every game, without the round logs.
+    /// Grows with the number of games.
+    Summary,
+    /// Every game with all of its round logs
+    Full,
+}
+
+impl fmt::Display for LogRetention {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                LogRetention::None => "None",
+                LogRetention::Summary => "Summary",
+                LogRetention::Full => "Full",
+            }
+        );
+    }
+}
@@ -1,6 +1,26 @@
+pub mod agent_comparison;
+pub mod balance_trajectory;
+pub mod distribution;
+pub mod even_money_rule;
+pub mod expected_value;
 pub mod game_configs;
 pub mod game_logs;
 pub mod game_runner;
+pub mod limit_policy;
+pub mod log_retention;
+pub mod payout_table;
 pub mod roulette_game;
 pub mod roulette_type;
+pub mod round_event;
+pub mod round_observer;
+pub mod ruin_stats;
+pub mod run_controls;
+pub mod run_result;
+pub mod spin_source;
 pub mod stats;
+pub mod stats_accumulator;
+pub mod sweep_config;
+pub mod sweep_point;
+pub mod sweep_result;
+pub mod sweep_runner;
+pub mod table_limits;
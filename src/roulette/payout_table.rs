@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bet::{bet_value::BetValue, naming_scheme::NamingScheme},
+    board::{slot::Slot, wheel::Wheel},
+    error::Error,
+    types::slot_number::SlotNumber,
+};
+
+use super::roulette_type::RouletteType;
+
+/// Keys of a payout table, the bet values of agents.json with adjacent
+/// numbers told apart by how many numbers they cover. The casino names of
+/// agents.json are accepted too.
+const PAYOUT_NAMES: [&str; 13] = [
+    "Color",
+    "Column",
+    "Corner",
+    "DoubleColumn",
+    "Dozen",
+    "EvenOdd",
+    "FirstFour",
+    "FiveNumber",
+    "Half",
+    "Number",
+    "Row",
+    "Split",
+    "Trio",
+];
+
+/// What every kind of bet pays, as the `35` of 35 to 1. A winning bet gets
+/// its stake back on top of the payout.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct PayoutTable {
+    pub payouts: BTreeMap<String, i64>,
+}
+
+impl PayoutTable {
+    pub fn european() -> Self {
+        return PayoutTable::from_entries(&[
+            ("Color", 1),
+            ("Column", 11),
+            ("Corner", 8),
+            ("DoubleColumn", 5),
+            ("Dozen", 2),
+            ("EvenOdd", 1),
+            ("FirstFour", 8),
+            ("FiveNumber", 6),
+            ("Half", 1),
+            ("Number", 35),
+            ("Row", 2),
+            ("Split", 17),
+            ("Trio", 11),
+        ]);
+    }
+
+    /// Same payouts as the European table, the extra double zero is where
+    /// the higher house edge comes from
+    pub fn american() -> Self {
+        return PayoutTable::european();
+    }
+
+    pub fn for_roulette_type(roulette_type: &RouletteType) -> Self {
+        return match roulette_type {
+            RouletteType::European => PayoutTable::european(),
+            RouletteType::American => PayoutTable::american(),
+        };
+    }
+
+    fn from_entries(entries: &[(&str, i64)]) -> Self {
+        return PayoutTable {
+            payouts: entries
+                .iter()
+                .map(|(payout_name, payout)| (String::from(*payout_name), *payout))
+                .collect(),
+        };
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        for (payout_name, payout) in self.payouts.iter() {
+            if !PAYOUT_NAMES.contains(&NamingScheme::to_classic_name(payout_name)) {
+                return Err(Error::GenericError {
+                    message: format!("Unknown bet type in payout table: {}", payout_name),
+                    nested_error: None,
+                });
+            }
+            if *payout < 1 {
+                return Err(Error::GenericError {
+                    message: format!(
+                        "Payout of {} must be at least 1 to 1, got {} to 1",
+                        payout_name, payout
+                    ),
+                    nested_error: None,
+                });
+            }
+        }
+        let missing_names: Vec<&str> = PAYOUT_NAMES
+            .iter()
+            .filter(|payout_name| self.get_payout(payout_name).is_none())
+            .copied()
+            .collect();
+        if !missing_names.is_empty() {
+            return Err(Error::GenericError {
+                message: format!("Payout table has no entry for {}", missing_names.join(", ")),
+                nested_error: None,
+            });
+        }
+        return Ok(());
+    }
+
+    /// Total amount returned (stake included) for `amount_cents` staked on
+    /// `bet_value`. Call and neighbour bets split the stake evenly over their
+    /// chips, any fraction of a cent is kept by the house.
+    pub fn get_payout_cents(
+        &self,
+        bet_value: &BetValue,
+        winning_slot: &Slot,
+        wheel: &Wheel,
+        amount_cents: i64,
+    ) -> i64 {
+        let (returned_chips, chips) = self.get_returned_chips(bet_value, winning_slot, wheel);
+        return amount_cents * returned_chips / chips;
+    }
+
+    /// Total amount returned per unit staked (stake included) when `bet_value`
+    /// covers the winning slot, 0 otherwise.
+    pub fn get_payout_multiplier(
+        &self,
+        bet_value: &BetValue,
+        winning_slot: &Slot,
+        wheel: &Wheel,
+    ) -> f64 {
+        let (returned_chips, chips) = self.get_returned_chips(bet_value, winning_slot, wheel);
+        return returned_chips as f64 / chips as f64;
+    }
+
+    /// Chips returned by the winning slot out of the chips the bet is made of
+    fn get_returned_chips(
+        &self,
+        bet_value: &BetValue,
+        winning_slot: &Slot,
+        wheel: &Wheel,
+    ) -> (i64, i64) {
+        let covered = bet_value.covers(winning_slot, wheel);
+        return match bet_value {
+            BetValue::CallBet(call_bet) => {
+                let returned_chips = call_bet
+                    .get_placements()
+                    .iter()
+                    .filter(|placement| placement.numbers.contains(&winning_slot.number))
+                    .map(|placement| {
+                        placement.chips
+                            * self.get_multiplier(Self::get_placement_name(&placement.numbers))
+                    })
+                    .sum();
+                (returned_chips, call_bet.get_chip_count())
+            }
+            BetValue::Neighbours { .. } => {
+                let chips = bet_value.get_chip_count();
+                if covered {
+                    (self.get_multiplier(Some("Number")), chips)
+                } else {
+                    (0, chips)
+                }
+            }
+            BetValue::AdjacentNumbers(adjacent_numbers) if covered => (
+                self.get_multiplier(Self::get_placement_name(&adjacent_numbers.numbers)),
+                1,
+            ),
+            _ if covered => (self.get_multiplier(Some(bet_value.get_variant_name())), 1),
+            _ => (0, 1),
+        };
+    }
+
+    fn get_multiplier(&self, payout_name: Option<&str>) -> i64 {
+        return payout_name
+            .and_then(|payout_name| self.get_payout(payout_name))
+            .map(|payout| payout + 1)
+            .unwrap_or(0);
+    }
+
+    /// Entry for the classic `payout_name`, which may be keyed by its casino name
+    fn get_payout(&self, payout_name: &str) -> Option<i64> {
+        return self
+            .payouts
+            .iter()
+            .find(|(name, _)| NamingScheme::to_classic_name(name) == payout_name)
+            .map(|(_, payout)| *payout);
+    }
+
+    /// Entry paying a bet on `numbers` next to each other on the table, as
+    /// accepted by `Bet::validate`
+    fn get_placement_name(numbers: &[SlotNumber]) -> Option<&'static str> {
+        return match numbers.len() {
+            1 => Some("Number"),
+            2 => Some("Split"),
+            3 => Some("Trio"),
+            4 if numbers.contains(&0) => Some("FirstFour"),
+            4 => Some("Corner"),
+            5 => Some("FiveNumber"),
+            _ => None,
+        };
+    }
+}
+
+impl Default for PayoutTable {
+    fn default() -> Self {
+        return PayoutTable::european();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::board::Board;
+    use crate::types::{adjacent_numbers::AdjacentNumbers, call_bet::CallBet, color::Color};
+
+    #[test]
+    fn test_default_multipliers() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let payout_table = PayoutTable::default();
+        assert!(payout_table.validate().is_ok());
+        let seventeen = board.slots.iter().find(|slot| slot.number == 17).unwrap();
+        assert_eq!(
+            payout_table.get_payout_multiplier(&BetValue::Number(17), seventeen, &board.wheel),
+            36.0
+        );
+        assert_eq!(
+            payout_table.get_payout_multiplier(&BetValue::Number(18), seventeen, &board.wheel),
+            0.0
+        );
+        assert_eq!(
+            payout_table.get_payout_multiplier(
+                &BetValue::Color(Color::Black),
+                seventeen,
+                &board.wheel
+            ),
+            2.0
+        );
+    }
+
+    #[test]
+    fn test_short_pay() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let mut payout_table = PayoutTable::european();
+        payout_table.payouts.insert(String::from("Number"), 34);
+        assert!(payout_table.validate().is_ok());
+        let seventeen = board.slots.iter().find(|slot| slot.number == 17).unwrap();
+        assert_eq!(
+            payout_table.get_payout_cents(&BetValue::Number(17), seventeen, &board.wheel, 1000),
+            35000
+        );
+    }
+
+    #[test]
+    fn test_call_bet_payouts() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let payout_table = PayoutTable::default();
+        let slot = |number: SlotNumber| {
+            board
+                .slots
+                .iter()
+                .find(|slot| slot.number == number)
+                .unwrap()
+        };
+        let orphelins = BetValue::CallBet(CallBet::Orphelins);
+        // 17 sits on two of the five splits
+        assert_eq!(
+            payout_table.get_payout_cents(&orphelins, slot(17), &board.wheel, 500),
+            3600
+        );
+        assert_eq!(
+            payout_table.get_payout_cents(&orphelins, slot(1), &board.wheel, 500),
+            3600
+        );
+        assert_eq!(
+            payout_table.get_payout_cents(&orphelins, slot(20), &board.wheel, 500),
+            1800
+        );
+        assert_eq!(
+            payout_table.get_payout_cents(&orphelins, slot(0), &board.wheel, 500),
+            0
+        );
+        let voisins = BetValue::CallBet(CallBet::VoisinsDuZero);
+        assert_eq!(
+            payout_table.get_payout_cents(&voisins, slot(0), &board.wheel, 900),
+            2400
+        );
+        assert_eq!(
+            payout_table.get_payout_cents(&voisins, slot(26), &board.wheel, 900),
+            1800
+        );
+        assert_eq!(
+            payout_table.get_payout_cents(&voisins, slot(4), &board.wheel, 900),
+            1800
+        );
+        let jeu_zero = BetValue::CallBet(CallBet::JeuZero);
+        assert_eq!(
+            payout_table.get_payout_cents(&jeu_zero, slot(26), &board.wheel, 400),
+            3600
+        );
+    }
+
+    #[test]
+    fn test_neighbours_payouts() {
+        let board = Board::generate(&RouletteType::American).unwrap();
+        let payout_table = PayoutTable::default();
+        let slot = |number: SlotNumber| {
+            board
+                .slots
+                .iter()
+                .find(|slot| slot.number == number)
+                .unwrap()
+        };
+        let neighbours = BetValue::Neighbours {
+            center: -1,
+            radius: 2,
+        };
+        assert_eq!(
+            payout_table.get_payout_cents(&neighbours, slot(13), &board.wheel, 500),
+            3600
+        );
+        assert_eq!(
+            payout_table.get_payout_cents(&neighbours, slot(27), &board.wheel, 500),
+            3600
+        );
+        assert_eq!(
+            payout_table.get_payout_cents(&neighbours, slot(36), &board.wheel, 500),
+            0
+        );
+    }
+
+    #[test]
+    fn test_top_line_payouts() {
+        let board = Board::generate(&RouletteType::American).unwrap();
+        let payout_table = PayoutTable::american();
+        let zero = &board.slots[0];
+        let bet_value =
+            |numbers: Vec<SlotNumber>| BetValue::AdjacentNumbers(AdjacentNumbers { numbers });
+        assert_eq!(
+            payout_table.get_payout_multiplier(
+                &bet_value(vec![-1, 0, 1, 2, 3]),
+                zero,
+                &board.wheel
+            ),
+            7.0
+        );
+        assert_eq!(
+            payout_table.get_payout_multiplier(&bet_value(vec![2, 0, -1]), zero, &board.wheel),
+            12.0
+        );
+        assert_eq!(
+            payout_table.get_payout_multiplier(&bet_value(vec![3, 2, 1, 0]), zero, &board.wheel),
+            9.0
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut payout_table = PayoutTable::european();
+        payout_table.payouts.remove("Split");
+        payout_table.payouts.remove("Dozen");
+        assert_eq!(
+            payout_table.validate().unwrap_err().get_message(),
+            "Payout table has no entry for Dozen, Split"
+        );
+
+        let mut payout_table = PayoutTable::european();
+        payout_table.payouts.remove("Column");
+        payout_table.payouts.insert(String::from("Street"), 11);
+        assert!(payout_table.validate().is_ok());
+
+        let mut payout_table = PayoutTable::european();
+        payout_table.payouts.insert(String::from("Basket"), 6);
+        assert!(payout_table.validate().is_err());
+
+        let mut payout_table = PayoutTable::european();
+        payout_table.payouts.insert(String::from("Number"), 0);
+        assert!(payout_table.validate().is_err());
+    }
+}
@@ -1,12 +1,20 @@
-use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 
 use crate::agent::{agent::Agent, agent_log::AgentLog};
-use crate::bet::{bet_log::BetLog, bet_state::BetState, bet_value::BetValue};
+use crate::bet::{bet_log::BetLog, bet_state::BetState};
 use crate::board::{board::Board, slot::Slot};
 use crate::error::Error;
+use crate::types::color::Color;
 
-use super::{game_logs::GameLog, roulette_type::RouletteType};
+use super::{
+    even_money_rule::EvenMoneyRule,
+    game_logs::GameLog,
+    payout_table::PayoutTable,
+    roulette_type::RouletteType,
+    round_observer::{NoopObserver, RoundObserver},
+    spin_source::SpinSource,
+    table_limits::TableLimits,
+};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct RouletteGame {
@@ -17,18 +25,29 @@ pub struct RouletteGame {
     pub allow_negative_balance: bool,
     pub game_logs: Vec<GameLog>,
     pub roulette_type: RouletteType,
+    pub even_money_rule: EvenMoneyRule,
+    pub table_limits: TableLimits,
+    pub payout_table: PayoutTable,
 }
 
 impl RouletteGame {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         game_number: i32,
         agents: Vec<Agent>,
         number_of_rounds: i32,
         allow_negative_balance: bool,
         roulette_type: Option<RouletteType>,
+        even_money_rule: Option<EvenMoneyRule>,
+        table_limits: Option<TableLimits>,
+        payout_table: Option<PayoutTable>,
     ) -> Result<Self, Error> {
         let roulette_type = roulette_type.unwrap_or(RouletteType::European);
+        let even_money_rule = even_money_rule.unwrap_or_default();
         let board = Board::generate(&roulette_type)?;
+        let payout_table =
+            payout_table.unwrap_or_else(|| PayoutTable::for_roulette_type(&roulette_type));
+        payout_table.validate()?;
         return Ok(RouletteGame {
             game_number,
             board,
@@ -37,27 +56,62 @@ impl RouletteGame {
             allow_negative_balance,
             game_logs: Vec::new(),
             roulette_type,
+            even_money_rule,
+            table_limits: table_limits.unwrap_or_default(),
+            payout_table,
         });
     }
 
-    pub fn play(&mut self) -> Result<(), Error> {
+    pub fn play(&mut self, spin_source: &mut dyn SpinSource) -> Result<(), Error> {
+        return self.play_observed(spin_source, &mut NoopObserver {});
+    }
+
+    pub fn play_observed(
+        &mut self,
+        spin_source: &mut dyn SpinSource,
+        observer: &mut dyn RoundObserver,
+    ) -> Result<(), Error> {
         self.consolidate_bets();
         self.validate_bets();
+        self.agents
+            .iter_mut()
+            .for_each(|agent| agent.start_session());
         for round_number in 1..=self.number_of_rounds {
-            self.play_round(round_number)?;
+            self.play_round(round_number, spin_source)?;
+            observer.on_round(self);
         }
         return Ok(());
     }
 
-    fn play_round(&mut self, round_number: i32) -> Result<(), Error> {
+    /// Drops the per-round logs of the game, its agents and their bets,
+    /// keeping only the final state
+    pub fn drop_round_logs(&mut self) {
+        self.game_logs = Vec::new();
+        for agent in self.agents.iter_mut() {
+            agent.agent_logs = Vec::new();
+            for bet in agent.strategic_bets.iter_mut() {
+                bet.bet_logs = Vec::new();
+            }
+        }
+    }
+
+    fn play_round(
+        &mut self,
+        round_number: i32,
+        spin_source: &mut dyn SpinSource,
+    ) -> Result<(), Error> {
+        self.check_bet_triggers();
+        self.apply_table_limits();
         if self.allow_negative_balance {
             self.allow_all_bets();
         } else {
             self.ensure_agent_funds();
         }
+        self.remove_exited_agents_bets();
         self.collect_bets();
-        let winning_slot = self.spin()?;
+        let winning_slot = self.spin(spin_source)?;
         self.determine_bet_results(&winning_slot);
+        self.check_exit_rules(round_number);
         self.log_round(round_number, &winning_slot);
         self.play_agent_strategies();
         return Ok(());
@@ -70,111 +124,79 @@ impl RouletteGame {
         });
 
         for agent in self.agents.iter_mut() {
+            let at_table = agent
+                .exit
+                .is_none_or(|exit| exit.round_number == round_number);
             agent.agent_logs.push(AgentLog {
                 round_number,
                 balance_cents: agent.balance_cents,
+                exit_reason: agent
+                    .exit
+                    .filter(|exit| exit.round_number == round_number)
+                    .map(|exit| exit.exit_reason),
+                out_of_funds: at_table && agent.is_out_of_funds(),
             });
             for bet in agent
                 .strategic_bets
                 .iter_mut()
                 .filter(|bet| bet.bet_state != BetState::Inactive)
+                .filter(|bet| bet.bet_state != BetState::Waiting)
+                .filter(|bet| bet.bet_state != BetState::Retired || bet.table_limit_hit)
             {
                 bet.bet_logs.push(BetLog {
                     round_number,
                     bet_state: bet.bet_state,
                     amount_cents: bet.amount_cents,
+                    payout_cents: bet.payout_cents,
+                    table_limit_hit: bet.table_limit_hit,
                 });
             }
         }
     }
 
     fn determine_bet_results(&mut self, winning_slot: &Slot) {
+        let is_zero = winning_slot.color == Color::Green;
+        let even_money_rule = self.even_money_rule;
+        let payout_table = &self.payout_table;
+        let wheel = &self.board.wheel;
         self.agents.iter_mut().for_each(|agent| {
-            agent
-                .strategic_bets
-                .iter_mut()
-                .filter(|bet| bet.bet_state == BetState::Active)
-                .for_each(|bet| match &bet.bet_value {
-                    BetValue::AdjacentNumbers(adjacent_numbers) => {
-                        if adjacent_numbers.numbers.contains(&winning_slot.number) {
-                            let num_count = adjacent_numbers.numbers.len();
+            agent.strategic_bets.iter_mut().for_each(|bet| {
+                bet.payout_cents = 0;
+                match bet.bet_state {
+                    BetState::Active => {
+                        if bet.bet_value.covers(winning_slot, wheel) {
+                            bet.payout_cents = payout_table.get_payout_cents(
+                                &bet.bet_value,
+                                winning_slot,
+                                wheel,
+                                bet.amount_cents,
+                            );
                             bet.bet_state = BetState::Won;
-                            if num_count == 2 {
-                                agent.balance_cents += bet.amount_cents * 18;
-                            } else if num_count == 3 {
-                                agent.balance_cents += bet.amount_cents * 12;
-                            } else if num_count == 4 {
-                                agent.balance_cents += bet.amount_cents * 9;
+                        } else if is_zero && bet.bet_value.is_even_money() {
+                            match even_money_rule {
+                                EvenMoneyRule::Standard => bet.bet_state = BetState::Lost,
+                                EvenMoneyRule::LaPartage | EvenMoneyRule::Surrender => {
+                                    bet.payout_cents = bet.amount_cents / 2;
+                                    bet.bet_state = BetState::Surrendered;
+                                }
+                                EvenMoneyRule::EnPrison => bet.bet_state = BetState::Imprisoned,
                             }
                         } else {
                             bet.bet_state = BetState::Lost;
                         }
                     }
-                    BetValue::Color(color) => {
-                        if *color == winning_slot.color {
-                            agent.balance_cents += bet.amount_cents * 2;
-                            bet.bet_state = BetState::Won;
-                        } else {
-                            bet.bet_state = BetState::Lost;
-                        }
-                    }
-                    BetValue::Column(column) => {
-                        if *column == winning_slot.column {
-                            agent.balance_cents += bet.amount_cents * 12;
-                            bet.bet_state = BetState::Won;
-                        } else {
-                            bet.bet_state = BetState::Lost;
-                        }
-                    }
-                    BetValue::Dozen(dozen) => {
-                        if *dozen == winning_slot.dozen {
-                            agent.balance_cents += bet.amount_cents * 3;
-                            bet.bet_state = BetState::Won;
-                        } else {
-                            bet.bet_state = BetState::Lost;
-                        }
-                    }
-                    BetValue::EvenOdd(even_odd) => {
-                        if *even_odd == winning_slot.even_odd {
-                            agent.balance_cents += bet.amount_cents * 2;
-                            bet.bet_state = BetState::Won;
+                    BetState::Imprisoned => {
+                        if bet.bet_value.covers(winning_slot, wheel) {
+                            bet.payout_cents = bet.amount_cents;
+                            bet.bet_state = BetState::Released;
                         } else {
-                            bet.bet_state = BetState::Lost;
-                        }
-                    }
-                    BetValue::Half(half) => {
-                        if *half == winning_slot.half {
-                            agent.balance_cents += bet.amount_cents * 2;
-                            bet.bet_state = BetState::Won;
-                        } else {
-                            bet.bet_state = BetState::Lost;
-                        }
-                    }
-                    BetValue::Number(number) => {
-                        if *number == winning_slot.number {
-                            agent.balance_cents += bet.amount_cents * 36;
-                            bet.bet_state = BetState::Won;
-                        } else {
-                            bet.bet_state = BetState::Lost;
-                        }
-                    }
-                    BetValue::Row(row) => {
-                        if *row == winning_slot.row {
-                            agent.balance_cents += bet.amount_cents * 3;
-                            bet.bet_state = BetState::Won;
-                        } else {
-                            bet.bet_state = BetState::Lost;
-                        }
-                    }
-                    BetValue::DoubleColumn(double_column) => {
-                        if double_column.columns.contains(&winning_slot.column) {
-                            agent.balance_cents += bet.amount_cents * 6;
-                            bet.bet_state = BetState::Won;
-                        } else {
-                            bet.bet_state = BetState::Lost;
+                            bet.bet_state = BetState::Forfeited;
                         }
                     }
-                });
+                    _ => {}
+                }
+                agent.balance_cents += bet.payout_cents;
+            });
         });
     }
 
@@ -190,20 +212,8 @@ impl RouletteGame {
         });
     }
 
-    fn spin(&mut self) -> Result<Slot, Error> {
-        let mut rng = rand::thread_rng();
-        return self
-            .board
-            .slots
-            .choose(&mut rng)
-            .ok_or(Error::GenericError {
-                message: format!(
-                    "Unable to choose a random slot from the board: {:?}",
-                    self.board,
-                ),
-                nested_error: None,
-            })
-            .cloned();
+    fn spin(&mut self, spin_source: &mut dyn SpinSource) -> Result<Slot, Error> {
+        return spin_source.next_slot(&self.board);
     }
 
     fn consolidate_bets(&mut self) {
@@ -213,12 +223,52 @@ impl RouletteGame {
     }
 
     fn validate_bets(&mut self) {
+        let table_limits = &self.table_limits;
         self.agents.iter_mut().for_each(|agent| {
             agent
                 .strategic_bets
                 .iter_mut()
                 .filter(|bet| bet.bet_state == BetState::Active)
-                .for_each(|bet| bet.validate(Some(&self.roulette_type)))
+                .for_each(|bet| {
+                    bet.validate(Some(&self.roulette_type));
+                    // inactive bets would be back in play next round
+                    if bet.bet_state == BetState::Inactive
+                        || table_limits.is_below_minimum(bet.initial_amount_cents)
+                    {
+                        bet.bet_state = BetState::Retired;
+                    }
+                })
+        });
+    }
+
+    /// Holds back the bets whose trigger isn't met by the spins so far
+    fn check_bet_triggers(&mut self) {
+        let game_logs = &self.game_logs;
+        let wheel = &self.board.wheel;
+        self.agents.iter_mut().for_each(|agent| {
+            agent
+                .strategic_bets
+                .iter_mut()
+                .filter(|bet| !matches!(bet.bet_state, BetState::Imprisoned | BetState::Retired))
+                .for_each(|bet| {
+                    if let Some(trigger) = &bet.trigger {
+                        bet.bet_state = if trigger.is_met(game_logs, wheel) {
+                            BetState::Active
+                        } else {
+                            BetState::Waiting
+                        };
+                    }
+                })
+        });
+    }
+
+    fn apply_table_limits(&mut self) {
+        let table_limits = &self.table_limits;
+        self.agents.iter_mut().for_each(|agent| {
+            agent
+                .strategic_bets
+                .iter_mut()
+                .for_each(|bet| bet.apply_table_limits(table_limits))
         });
     }
 
@@ -234,22 +284,46 @@ impl RouletteGame {
             .for_each(|agent| agent.determine_affordable_bets());
     }
 
+    fn remove_exited_agents_bets(&mut self) {
+        self.agents
+            .iter_mut()
+            .filter(|agent| agent.has_exited())
+            .for_each(|agent| agent.leave_table());
+    }
+
+    fn check_exit_rules(&mut self, round_number: i32) {
+        self.agents
+            .iter_mut()
+            .for_each(|agent| agent.check_exit_rules(round_number));
+    }
+
     fn play_agent_strategies(&mut self) {
+        let game_logs = &self.game_logs;
         self.agents.iter_mut().for_each(|agent: &mut Agent| {
-            agent.play_strategy();
+            agent.play_strategy(game_logs);
         });
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
+
     use crate::{
-        agent::agent::Agent,
-        bet::{bet::Bet, bet_state::BetState, bet_value::BetValue},
+        agent::{agent::Agent, agent_exit::AgentExit, exit_reason::ExitReason},
+        bet::{bet::Bet, bet_state::BetState, bet_trigger::BetTrigger, bet_value::BetValue},
+        board::board::Board,
         json::deserializable::I64Deserializable,
-        roulette::roulette_type::RouletteType,
+        roulette::{
+            even_money_rule::EvenMoneyRule,
+            limit_policy::LimitPolicy,
+            roulette_type::RouletteType,
+            spin_source::{FixedSpinSource, RandomSpinSource},
+            table_limits::TableLimits,
+        },
+        strategy::strategy_type::StrategyType,
         types::{
-            adjacent_numbers::AdjacentNumbers, color::Color, column::Column,
+            adjacent_numbers::AdjacentNumbers, call_bet::CallBet, color::Color, column::Column,
             double_column::DoubleColumn, dozen::Dozen, even_odd::EvenOdd, half::Half, row::Row,
         },
     };
@@ -265,7 +339,11 @@ mod test {
                     bet_state: BetState::Active,
                     bet_value: BetValue::Color(Color::Red),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 }];
                 let strategic_bets_2 = vec![Bet {
                     amount_cents: 1000,
@@ -273,7 +351,11 @@ mod test {
                     bet_state: BetState::Active,
                     bet_value: BetValue::Number(17),
                     initial_amount_cents: 1000,
+                    payout_cents: 0,
                     progression_factor: 2,
+                    strategy: StrategyType::default(),
+                    trigger: None,
+                    table_limit_hit: false,
                 }];
                 vec![
                     Agent {
@@ -281,17 +363,28 @@ mod test {
                         strategic_bets: strategic_bets_1,
                         name: String::from("AGENT1"),
                         agent_logs: Vec::new(),
+                        ..Default::default()
                     },
                     Agent {
                         balance_cents: 100000,
                         strategic_bets: strategic_bets_2,
                         name: String::from("AGENT2"),
                         agent_logs: Vec::new(),
+                        ..Default::default()
                     },
                 ]
             }
         };
-        let res = RouletteGame::new(1, agents, 10, false, Some(RouletteType::European));
+        let res = RouletteGame::new(
+            1,
+            agents,
+            10,
+            false,
+            Some(RouletteType::European),
+            None,
+            None,
+            None,
+        );
         assert!(res.is_ok());
         return res.unwrap();
     }
@@ -309,10 +402,15 @@ mod test {
                         bet_state: BetState::Active,
                         bet_value: bet_value.clone(),
                         initial_amount_cents: 1000,
+                        payout_cents: 0,
                         progression_factor: 2,
+                        strategy: StrategyType::default(),
+                        trigger: None,
+                        table_limit_hit: false,
                     }],
                     name: format!("AGENT-{}", index + 1),
                     agent_logs: Vec::new(),
+                    ..Default::default()
                 })
                 .collect();
             agents
@@ -375,6 +473,47 @@ mod test {
         assert_eq!(game.agents[0].balance_cents, 136000);
     }
 
+    #[test]
+    fn test_determine_bet_results_short_pay_number_win() {
+        let mut game = create_game(None);
+        game.payout_table.payouts.insert(String::from("Number"), 34);
+        let slot = game.board.slots[1].clone();
+        assign_agents(&mut game, vec![BetValue::Number(slot.number)]);
+        game.determine_bet_results(&slot);
+        assert_eq!(game.agents[0].strategic_bets[0].bet_state, BetState::Won);
+        assert_eq!(game.agents[0].balance_cents, 135000);
+    }
+
+    #[test]
+    fn test_determine_bet_results_call_bet() {
+        let mut game = create_game(None);
+        let slot = game
+            .board
+            .slots
+            .iter()
+            .find(|slot| slot.number == 5)
+            .unwrap()
+            .clone();
+        assign_agents(
+            &mut game,
+            vec![
+                BetValue::CallBet(CallBet::TiersDuCylindre),
+                BetValue::CallBet(CallBet::JeuZero),
+            ],
+        );
+        // 600 splits evenly over the six chips of Tiers and the four of Jeu Zero
+        game.agents.iter_mut().for_each(|agent| {
+            agent.strategic_bets[0].amount_cents = 600;
+            agent.strategic_bets[0].initial_amount_cents = 600;
+        });
+        game.determine_bet_results(&slot);
+        assert_eq!(game.agents[0].strategic_bets[0].bet_state, BetState::Won);
+        // the 5/8 split pays 17 to 1 on its single chip
+        assert_eq!(game.agents[0].balance_cents, 101800);
+        assert_eq!(game.agents[1].strategic_bets[0].bet_state, BetState::Lost);
+        assert_eq!(game.agents[1].balance_cents, 100000);
+    }
+
     #[test]
     fn test_determine_bet_results_number_lose() {
         let mut game = create_game(None);
@@ -555,8 +694,7 @@ mod test {
             .board
             .slots
             .iter()
-            .filter(|slot| slot.number == 1)
-            .next()
+            .find(|slot| slot.number == 1)
             .unwrap()
             .clone();
 
@@ -578,8 +716,7 @@ mod test {
             .board
             .slots
             .iter()
-            .filter(|slot| slot.number == 1)
-            .next()
+            .find(|slot| slot.number == 1)
             .unwrap()
             .clone();
 
@@ -601,8 +738,7 @@ mod test {
             .board
             .slots
             .iter()
-            .filter(|slot| slot.number == 1)
-            .next()
+            .find(|slot| slot.number == 1)
             .unwrap()
             .clone();
         assign_agents(
@@ -623,8 +759,7 @@ mod test {
             .board
             .slots
             .iter()
-            .filter(|slot| slot.number == 29)
-            .next()
+            .find(|slot| slot.number == 29)
             .unwrap()
             .clone();
         assign_agents(
@@ -645,8 +780,7 @@ mod test {
             .board
             .slots
             .iter()
-            .filter(|slot| slot.number == 1)
-            .next()
+            .find(|slot| slot.number == 1)
             .unwrap()
             .clone();
         assign_agents(
@@ -667,8 +801,7 @@ mod test {
             .board
             .slots
             .iter()
-            .filter(|slot| slot.number == 1)
-            .next()
+            .find(|slot| slot.number == 1)
             .unwrap()
             .clone();
         assign_agents(
@@ -682,6 +815,173 @@ mod test {
         assert_eq!(game.agents[0].balance_cents, 100000);
     }
 
+    fn find_slot(game: &RouletteGame, number: i8) -> crate::board::slot::Slot {
+        return game
+            .board
+            .slots
+            .iter()
+            .find(|slot| slot.number == number)
+            .unwrap()
+            .clone();
+    }
+
+    #[test]
+    fn test_determine_bet_results_zero_standard() {
+        let mut game = create_game(None);
+        let zero = find_slot(&game, 0);
+        assign_agents(&mut game, vec![BetValue::Color(Color::Red)]);
+        game.determine_bet_results(&zero);
+        assert_eq!(game.agents[0].strategic_bets[0].bet_state, BetState::Lost);
+        assert_eq!(game.agents[0].balance_cents, 100000);
+    }
+
+    #[test]
+    fn test_determine_bet_results_la_partage() {
+        let mut game = create_game(None);
+        game.even_money_rule = EvenMoneyRule::LaPartage;
+        let zero = find_slot(&game, 0);
+        assign_agents(
+            &mut game,
+            vec![BetValue::EvenOdd(EvenOdd::Even), BetValue::Number(5)],
+        );
+        game.determine_bet_results(&zero);
+        assert_eq!(
+            game.agents[0].strategic_bets[0].bet_state,
+            BetState::Surrendered
+        );
+        assert_eq!(game.agents[0].balance_cents, 100500);
+        assert_eq!(game.agents[1].strategic_bets[0].bet_state, BetState::Lost);
+        assert_eq!(game.agents[1].balance_cents, 100000);
+    }
+
+    #[test]
+    fn test_determine_bet_results_surrender_double_zero() {
+        let mut game = create_game(None);
+        game.board = Board::generate(&RouletteType::American).unwrap();
+        game.roulette_type = RouletteType::American;
+        game.even_money_rule = EvenMoneyRule::Surrender;
+        let double_zero = find_slot(&game, -1);
+        assign_agents(&mut game, vec![BetValue::Half(Half::One)]);
+        game.determine_bet_results(&double_zero);
+        assert_eq!(
+            game.agents[0].strategic_bets[0].bet_state,
+            BetState::Surrendered
+        );
+        assert_eq!(game.agents[0].balance_cents, 100500);
+    }
+
+    #[test]
+    fn test_en_prison_released() {
+        let mut game = create_game(None);
+        game.even_money_rule = EvenMoneyRule::EnPrison;
+        assign_agents(&mut game, vec![BetValue::Color(Color::Red)]);
+        game.play(&mut FixedSpinSource::new(vec![0, 1, 1])).unwrap();
+        let bet = &game.agents[0].strategic_bets[0];
+        let bet_states: Vec<BetState> = bet.bet_logs.iter().map(|log| log.bet_state).collect();
+        assert_eq!(
+            bet_states[..3],
+            [BetState::Imprisoned, BetState::Released, BetState::Won]
+        );
+        assert_eq!(game.agents[0].agent_logs[0].balance_cents, 99000);
+        assert_eq!(game.agents[0].agent_logs[1].balance_cents, 100000);
+        assert_eq!(game.agents[0].agent_logs[2].balance_cents, 101000);
+    }
+
+    #[test]
+    fn test_en_prison_forfeited() {
+        let mut game = create_game(None);
+        game.even_money_rule = EvenMoneyRule::EnPrison;
+        assign_agents(&mut game, vec![BetValue::Color(Color::Red)]);
+        game.play(&mut FixedSpinSource::new(vec![0, 0, 1])).unwrap();
+        let bet = &game.agents[0].strategic_bets[0];
+        assert_eq!(bet.bet_logs[0].bet_state, BetState::Imprisoned);
+        assert_eq!(bet.bet_logs[1].bet_state, BetState::Forfeited);
+        assert_eq!(bet.bet_logs[2].amount_cents, 2000);
+        assert_eq!(game.agents[0].agent_logs[1].balance_cents, 99000);
+    }
+
+    #[test]
+    fn test_stop_loss_exit() {
+        let mut game = create_game(None);
+        assign_agents(&mut game, vec![BetValue::Color(Color::Red)]);
+        game.agents[0].exit_rules.stop_loss_cents = Some(2500);
+        game.play(&mut FixedSpinSource::new(vec![2])).unwrap();
+        let agent = &game.agents[0];
+        assert_eq!(
+            agent.exit,
+            Some(AgentExit {
+                round_number: 2,
+                exit_reason: ExitReason::StopLoss
+            })
+        );
+        assert_eq!(agent.agent_logs[0].exit_reason, None);
+        assert_eq!(agent.agent_logs[1].exit_reason, Some(ExitReason::StopLoss));
+        assert_eq!(agent.agent_logs[9].exit_reason, None);
+        assert!(agent
+            .agent_logs
+            .iter()
+            .skip(1)
+            .all(|agent_log| agent_log.balance_cents == 97000));
+        assert_eq!(agent.strategic_bets[0].bet_logs.len(), 2);
+    }
+
+    #[test]
+    fn test_max_spins_and_take_profit_exit() {
+        let mut game = create_game(None);
+        assign_agents(
+            &mut game,
+            vec![BetValue::Color(Color::Red), BetValue::Color(Color::Red)],
+        );
+        game.agents[0].exit_rules.max_spins = Some(3);
+        game.agents[1].exit_rules.take_profit_cents = Some(2000);
+        game.play(&mut FixedSpinSource::new(vec![1])).unwrap();
+        assert_eq!(game.agents[0].exit_reason(), ExitReason::MaxSpins);
+        assert_eq!(game.agents[0].balance_cents, 103000);
+        assert_eq!(game.agents[1].exit_reason(), ExitReason::TakeProfit);
+        assert_eq!(game.agents[1].exit.unwrap().round_number, 2);
+        assert_eq!(game.agents[1].balance_cents, 102000);
+    }
+
+    #[test]
+    fn test_table_maximum_stops_progression() {
+        let mut game = create_game(None);
+        assign_agents(&mut game, vec![BetValue::Color(Color::Red)]);
+        game.table_limits = TableLimits {
+            bet_type_maximums_cents: BTreeMap::from([("Color".to_string(), 4000)]),
+            limit_policy: LimitPolicy::Stop,
+            ..TableLimits::default()
+        };
+        game.play(&mut FixedSpinSource::new(vec![2])).unwrap();
+        let bet = &game.agents[0].strategic_bets[0];
+        assert_eq!(bet.bet_logs.len(), 4);
+        assert!(!bet.bet_logs[2].table_limit_hit);
+        assert!(bet.bet_logs[3].table_limit_hit);
+        assert_eq!(bet.bet_logs[3].bet_state, BetState::Retired);
+        assert_eq!(game.agents[0].balance_cents, 93000);
+    }
+
+    #[test]
+    fn test_table_maximum_clamps_progression() {
+        let mut game = create_game(None);
+        assign_agents(&mut game, vec![BetValue::Color(Color::Red)]);
+        game.table_limits.bet_type_maximums_cents = BTreeMap::from([("Color".to_string(), 4000)]);
+        game.play(&mut FixedSpinSource::new(vec![2])).unwrap();
+        let bet = &game.agents[0].strategic_bets[0];
+        assert_eq!(bet.bet_logs.len(), 10);
+        assert!(bet.bet_logs[3..]
+            .iter()
+            .all(|bet_log| bet_log.amount_cents == 4000 && bet_log.table_limit_hit));
+    }
+
+    #[test]
+    fn test_table_minimum() {
+        let mut game = create_game(None);
+        game.table_limits.table_minimum_cents = Some(1500);
+        game.play(&mut FixedSpinSource::new(vec![17])).unwrap();
+        assert!(game.agents[1].strategic_bets[0].bet_logs.is_empty());
+        assert_eq!(game.agents[1].balance_cents, 100000);
+    }
+
     #[test]
     fn test_collect_bets_active() {
         let mut game = create_game(None);
@@ -701,9 +1001,55 @@ mod test {
     #[test]
     fn test_spin() {
         let mut game = create_game(None);
-        let res = game.spin();
+        let res = game.spin(&mut RandomSpinSource::from_entropy());
         assert!(res.is_ok());
-        assert_eq!(game.board.slots.contains(&res.unwrap()), true);
+        assert!(game.board.slots.contains(&res.unwrap()));
+    }
+
+    #[test]
+    fn test_play_seeded_is_reproducible() {
+        let mut first_game = create_game(None);
+        let mut second_game = create_game(None);
+        first_game
+            .play(&mut RandomSpinSource::seeded(7, first_game.game_number))
+            .unwrap();
+        second_game
+            .play(&mut RandomSpinSource::seeded(7, second_game.game_number))
+            .unwrap();
+        assert_eq!(first_game, second_game);
+    }
+
+    #[test]
+    fn test_play_fixed_spins() {
+        let mut game = create_game(None);
+        game.play(&mut FixedSpinSource::new(vec![17])).unwrap();
+        assert_eq!(game.game_logs.len(), 10);
+        assert!(game
+            .game_logs
+            .iter()
+            .all(|game_log| game_log.winning_slot.number == 17));
+        assert_eq!(game.agents[1].balance_cents, 100000 + 10 * 35000);
+    }
+
+    #[test]
+    fn test_bet_trigger() {
+        let mut game = create_game(None);
+        assign_agents(&mut game, vec![BetValue::Color(Color::Black)]);
+        let bet = &mut game.agents[0].strategic_bets[0];
+        bet.progression_factor = 1;
+        bet.trigger = Some(BetTrigger::Streak {
+            of: BetValue::Color(Color::Red),
+            length: 2,
+        });
+        game.play(&mut FixedSpinSource::new(vec![1])).unwrap();
+        let bet = &game.agents[0].strategic_bets[0];
+        assert_eq!(bet.bet_logs.len(), 8);
+        assert!(bet
+            .bet_logs
+            .iter()
+            .all(|bet_log| bet_log.bet_state == BetState::Lost));
+        assert_eq!(game.agents[0].agent_logs[1].balance_cents, 100000);
+        assert_eq!(game.agents[0].balance_cents, 92000);
     }
 
     #[test]
@@ -717,4 +1063,18 @@ mod test {
             BetState::Inactive
         );
     }
+
+    #[test]
+    fn test_ruin() {
+        let mut game = create_game(None);
+        assign_agents(&mut game, vec![BetValue::Color(Color::Red)]);
+        game.agents[0].balance_cents = 3000;
+        game.play(&mut FixedSpinSource::new(vec![2])).unwrap();
+        let agent = &game.agents[0];
+        assert_eq!(agent.agent_logs[1].balance_cents, 0);
+        assert!(!agent.agent_logs[1].out_of_funds);
+        assert!(agent.agent_logs[2].out_of_funds);
+        assert_eq!(agent.ruin_round(), Some(2));
+        assert_eq!(agent.max_drawdown_cents(), 3000);
+    }
 }
@@ -1,7 +1,35 @@
+use core::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum RouletteType {
     American,
     European,
 }
+
+impl fmt::Display for RouletteType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                RouletteType::American => "American",
+                RouletteType::European => "European",
+            }
+        );
+    }
+}
+
+impl FromStr for RouletteType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "american" => Ok(RouletteType::American),
+            "european" => Ok(RouletteType::European),
+            _ => Err(format!("{} is not a valid roulette type", s)),
+        }
+    }
+}
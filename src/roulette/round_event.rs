@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agent::exit_reason::ExitReason;
+use crate::board::slot::Slot;
+
+use super::{
+    even_money_rule::EvenMoneyRule, roulette_game::RouletteGame, roulette_type::RouletteType,
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct AgentBalance {
+    pub agent_name: String,
+    pub balance_cents: i64,
+    pub exit_reason: Option<ExitReason>,
+}
+
+/// Outcome of a single round as it is played, for live consumers.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct RoundEvent {
+    /// Game numbers start over for every wheel type and even money rule of a run
+    pub game_number: i32,
+    pub roulette_type: RouletteType,
+    pub even_money_rule: EvenMoneyRule,
+    pub round_number: i32,
+    pub winning_slot: Slot,
+    pub agent_balances: Vec<AgentBalance>,
+}
+
+impl RoundEvent {
+    /// Builds the event of the latest logged round of `game`
+    pub fn from_latest_round(game: &RouletteGame) -> Option<Self> {
+        let game_log = game.game_logs.last()?;
+        let agent_balances = game
+            .agents
+            .iter()
+            .filter_map(|agent| {
+                let agent_log = agent.agent_logs.last()?;
+                Some(AgentBalance {
+                    agent_name: agent.name.clone(),
+                    balance_cents: agent_log.balance_cents,
+                    exit_reason: agent_log.exit_reason,
+                })
+            })
+            .collect();
+        return Some(RoundEvent {
+            game_number: game.game_number,
+            roulette_type: game.roulette_type,
+            even_money_rule: game.even_money_rule,
+            round_number: game_log.round_number,
+            winning_slot: game_log.winning_slot.clone(),
+            agent_balances,
+        });
+    }
+}
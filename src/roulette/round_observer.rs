@@ -0,0 +1,73 @@
+use tokio::sync::broadcast;
+
+use super::{roulette_game::RouletteGame, round_event::RoundEvent};
+
+/// Gets told about every round right after it has been logged.
+pub trait RoundObserver: Send {
+    fn on_round(&mut self, game: &RouletteGame);
+}
+
+/// Ignores every round
+#[derive(Debug, Clone, Copy)]
+pub struct NoopObserver {}
+
+impl RoundObserver for NoopObserver {
+    fn on_round(&mut self, _game: &RouletteGame) {}
+}
+
+/// Publishes every round on a broadcast channel, as long as someone listens.
+#[derive(Debug, Clone)]
+pub struct BroadcastObserver {
+    sender: broadcast::Sender<RoundEvent>,
+}
+
+impl BroadcastObserver {
+    pub fn new(sender: broadcast::Sender<RoundEvent>) -> Self {
+        return BroadcastObserver { sender };
+    }
+}
+
+impl RoundObserver for BroadcastObserver {
+    fn on_round(&mut self, game: &RouletteGame) {
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        if let Some(round_event) = RoundEvent::from_latest_round(game) {
+            // only fails when every receiver has gone away in the meantime
+            let _ = self.sender.send(round_event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roulette::{roulette_type::RouletteType, spin_source::FixedSpinSource};
+
+    #[test]
+    fn test_broadcast_observer() {
+        let (sender, mut receiver) = broadcast::channel(16);
+        let mut game = RouletteGame::new(
+            3,
+            Vec::new(),
+            2,
+            false,
+            Some(RouletteType::European),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        game.play_observed(
+            &mut FixedSpinSource::new(vec![17, 4]),
+            &mut BroadcastObserver::new(sender),
+        )
+        .unwrap();
+        let first = receiver.try_recv().unwrap();
+        let second = receiver.try_recv().unwrap();
+        assert_eq!((first.game_number, first.round_number), (3, 1));
+        assert_eq!(first.winning_slot.number, 17);
+        assert_eq!(second.winning_slot.number, 4);
+        assert!(receiver.try_recv().is_err());
+    }
+}
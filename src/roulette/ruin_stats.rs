@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use super::distribution::{Distribution, DistributionTotals};
+
+/// How often and how fast an agent went broke over a set of games
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RuinStats {
+    /// Share of games the agent ran out of money or couldn't afford any bet
+    pub ruin_share: f64,
+    /// Rounds the ruined games went broke on
+    pub ruin_rounds: Option<Distribution>,
+    /// Share of games the agent was still solvent after each round, starting at round 1
+    pub survival_curve: Vec<f64>,
+    /// Largest fall from peak balance of every game, in cents
+    pub max_drawdowns: Option<Distribution>,
+}
+
+impl RuinStats {
+    /// Share still solvent after `round_number`, everyone survives round 0
+    pub fn get_survival(&self, round_number: usize) -> f64 {
+        if round_number == 0 {
+            return 1.0;
+        }
+        return self
+            .survival_curve
+            .get(round_number - 1)
+            .or(self.survival_curve.last())
+            .copied()
+            .unwrap_or(1.0);
+    }
+}
+
+/// Running totals of the ruin of an agent over the games added so far
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RuinTotals {
+    number_of_games: i64,
+    ruined_games: i64,
+    ruin_rounds: DistributionTotals,
+    /// Games that went broke on each round, indexed by round number - 1
+    ruins_per_round: Vec<i64>,
+    max_drawdowns_cents: DistributionTotals,
+}
+
+impl RuinTotals {
+    pub fn add_session(
+        &mut self,
+        ruin_round: Option<i32>,
+        max_drawdown_cents: i64,
+        number_of_rounds: i32,
+    ) {
+        self.number_of_games += 1;
+        let number_of_rounds = number_of_rounds.max(0) as usize;
+        if self.ruins_per_round.len() < number_of_rounds {
+            self.ruins_per_round.resize(number_of_rounds, 0);
+        }
+        if let Some(ruin_round) = ruin_round {
+            self.ruined_games += 1;
+            self.ruin_rounds.add(ruin_round as i64);
+            self.ruins_per_round[ruin_round as usize - 1] += 1;
+        }
+        self.max_drawdowns_cents.add(max_drawdown_cents);
+    }
+
+    pub fn to_ruin_stats(&self) -> RuinStats {
+        let number_of_games = self.number_of_games as f64;
+        let mut ruined_games = 0;
+        let survival_curve = self
+            .ruins_per_round
+            .iter()
+            .map(|ruins| {
+                ruined_games += ruins;
+                1.0 - ruined_games as f64 / number_of_games
+            })
+            .collect();
+        return RuinStats {
+            ruin_share: self.ruined_games as f64 / number_of_games,
+            ruin_rounds: self.ruin_rounds.to_distribution(),
+            survival_curve,
+            max_drawdowns: self.max_drawdowns_cents.to_distribution(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_ruin_stats() {
+        let mut ruin_totals = RuinTotals::default();
+        ruin_totals.add_session(Some(2), 5000, 4);
+        ruin_totals.add_session(None, 1000, 4);
+        ruin_totals.add_session(Some(4), 3000, 4);
+        ruin_totals.add_session(Some(2), 2000, 4);
+        let ruin_stats = ruin_totals.to_ruin_stats();
+        assert_eq!(ruin_stats.ruin_share, 0.75);
+        assert_eq!(ruin_stats.ruin_rounds.as_ref().unwrap().median, 2.0);
+        assert_eq!(ruin_stats.survival_curve, vec![1.0, 0.5, 0.5, 0.25]);
+        assert_eq!(ruin_stats.get_survival(0), 1.0);
+        assert_eq!(ruin_stats.get_survival(3), 0.5);
+        assert_eq!(ruin_stats.get_survival(10), 0.25);
+        assert_eq!(ruin_stats.max_drawdowns.unwrap().max, 5000);
+    }
+
+    #[test]
+    fn test_add_session_without_rounds() {
+        let mut ruin_totals = RuinTotals::default();
+        ruin_totals.add_session(None, 0, -1);
+        let ruin_stats = ruin_totals.to_ruin_stats();
+        assert!(ruin_stats.survival_curve.is_empty());
+        assert_eq!(ruin_stats.ruin_share, 0.0);
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use tokio::sync::broadcast;
+
+use super::round_event::RoundEvent;
+
+/// Hooks a caller can hand to a run to steer and watch it while it plays.
+#[derive(Debug, Clone, Default)]
+pub struct RunControls {
+    /// Games that haven't started yet are skipped once set
+    pub cancelled: Arc<AtomicBool>,
+    /// Receives every round of every game as it is played
+    pub round_events: Option<broadcast::Sender<RoundEvent>>,
+}
+
+impl RunControls {
+    pub fn is_cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::SeqCst);
+    }
+}
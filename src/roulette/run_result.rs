@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use super::{roulette_game::RouletteGame, stats::Stats};
+
+/// Outcome of a run, the stats cover every game that finished
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub stats: Stats,
+    /// Finished games as kept by the run's `LogRetention`
+    pub games: Vec<RouletteGame>,
+    pub failed_games: i32,
+    /// Rounds played over all games, at every table of a game when the agents
+    /// play common random numbers
+    pub spins: i64,
+    pub elapsed: Duration,
+}
+
+impl RunResult {
+    pub fn get_spins_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            return self.spins as f64 / seconds;
+        }
+        return 0.0;
+    }
+}
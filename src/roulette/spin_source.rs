@@ -0,0 +1,174 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::board::{board::Board, slot::Slot};
+use crate::error::Error;
+use crate::roulette::game_logs::GameLog;
+use crate::types::slot_number::SlotNumber;
+
+/// Decides which slot of the board wins each spin.
+pub trait SpinSource: Send {
+    fn next_slot(&mut self, board: &Board) -> Result<Slot, Error>;
+}
+
+#[derive(Debug, Clone)]
+pub struct RandomSpinSource {
+    rng: StdRng,
+}
+
+impl RandomSpinSource {
+    pub fn from_entropy() -> Self {
+        return RandomSpinSource {
+            rng: StdRng::from_entropy(),
+        };
+    }
+
+    /// Creates the RNG stream of a single game, so every game of a seeded run
+    /// is reproducible no matter which order the games are scheduled in.
+    pub fn seeded(seed: u64, game_number: i32) -> Self {
+        return RandomSpinSource {
+            rng: StdRng::seed_from_u64(derive_game_seed(seed, game_number)),
+        };
+    }
+
+    pub fn for_game(seed: Option<u64>, game_number: i32) -> Self {
+        return match seed {
+            Some(seed) => Self::seeded(seed, game_number),
+            None => Self::from_entropy(),
+        };
+    }
+}
+
+impl SpinSource for RandomSpinSource {
+    fn next_slot(&mut self, board: &Board) -> Result<Slot, Error> {
+        return board
+            .slots
+            .choose(&mut self.rng)
+            .ok_or(Error::GenericError {
+                message: format!("Unable to choose a random slot from the board: {:?}", board),
+                nested_error: None,
+            })
+            .cloned();
+    }
+}
+
+/// Replays a fixed sequence of winning numbers, starting over once it runs out.
+#[derive(Debug, Clone)]
+pub struct FixedSpinSource {
+    numbers: Vec<SlotNumber>,
+    position: usize,
+}
+
+impl FixedSpinSource {
+    pub fn new(numbers: Vec<SlotNumber>) -> Self {
+        return FixedSpinSource {
+            numbers,
+            position: 0,
+        };
+    }
+
+    /// Replays the winning slots of a game that was already played
+    pub fn from_game_logs(game_logs: &[GameLog]) -> Self {
+        return Self::new(
+            game_logs
+                .iter()
+                .map(|game_log| game_log.winning_slot.number)
+                .collect(),
+        );
+    }
+}
+
+impl SpinSource for FixedSpinSource {
+    fn next_slot(&mut self, board: &Board) -> Result<Slot, Error> {
+        if self.numbers.is_empty() {
+            return Err(Error::GenericError {
+                message: "Fixed spin source has no numbers to spin".to_string(),
+                nested_error: None,
+            });
+        }
+        let number = self.numbers[self.position % self.numbers.len()];
+        self.position += 1;
+        return board
+            .slots
+            .iter()
+            .find(|slot| slot.number == number)
+            .ok_or(Error::GenericError {
+                message: format!("{} is not a slot on the board", number),
+                nested_error: None,
+            })
+            .cloned();
+    }
+}
+
+// SplitMix64 finalizer, spreads neighbouring game numbers over unrelated seeds
+fn derive_game_seed(seed: u64, game_number: i32) -> u64 {
+    let mut z = seed.wrapping_add((game_number as u64).wrapping_mul(0x9E3779B97F4A7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    return z ^ (z >> 31);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roulette::roulette_type::RouletteType;
+
+    fn spin_numbers(spin_source: &mut dyn SpinSource, board: &Board, count: usize) -> Vec<i8> {
+        return (0..count)
+            .map(|_| spin_source.next_slot(board).unwrap().number)
+            .collect();
+    }
+
+    #[test]
+    fn test_seeded_is_reproducible() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let first = spin_numbers(&mut RandomSpinSource::seeded(42, 1), &board, 50);
+        let second = spin_numbers(&mut RandomSpinSource::seeded(42, 1), &board, 50);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_seeded_streams_differ_per_game() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let first = spin_numbers(&mut RandomSpinSource::seeded(42, 1), &board, 50);
+        let second = spin_numbers(&mut RandomSpinSource::seeded(42, 2), &board, 50);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_fixed_spin_source_cycles() {
+        let board = Board::generate(&RouletteType::American).unwrap();
+        let mut spin_source = FixedSpinSource::new(vec![17, -1, 0]);
+        assert_eq!(
+            spin_numbers(&mut spin_source, &board, 5),
+            vec![17, -1, 0, 17, -1]
+        );
+    }
+
+    #[test]
+    fn test_fixed_spin_source_from_game_logs() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let game_logs: Vec<GameLog> = [32, 15, 19]
+            .iter()
+            .enumerate()
+            .map(|(index, number)| GameLog {
+                round_number: index as i32 + 1,
+                winning_slot: board
+                    .slots
+                    .iter()
+                    .find(|slot| slot.number == *number)
+                    .unwrap()
+                    .clone(),
+            })
+            .collect();
+        let mut spin_source = FixedSpinSource::from_game_logs(&game_logs);
+        assert_eq!(spin_numbers(&mut spin_source, &board, 3), vec![32, 15, 19]);
+    }
+
+    #[test]
+    fn test_fixed_spin_source_invalid_number() {
+        let board = Board::generate(&RouletteType::European).unwrap();
+        let mut spin_source = FixedSpinSource::new(vec![-1]);
+        assert!(spin_source.next_slot(&board).is_err());
+        assert!(FixedSpinSource::new(vec![]).next_slot(&board).is_err());
+    }
+}
@@ -1,28 +1,59 @@
 use core::fmt;
+use itertools::Itertools;
 use prettytable::{Cell, Row, Table};
 use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 
-use super::roulette_game::RouletteGame;
-use crate::bet::{bet::Bet, bet_state::BetState};
+use super::{
+    agent_comparison::AgentComparison, balance_trajectory::BalanceTrajectory,
+    distribution::Distribution, even_money_rule::EvenMoneyRule, expected_value::AgentExpectation,
+    roulette_type::RouletteType, ruin_stats::RuinStats,
+};
+use crate::agent::exit_reason::ExitReason;
+use crate::bet::{bet::Bet, naming_scheme::NamingScheme};
+
+/// Columns of the survival curve table
+const SURVIVAL_CHECKPOINTS: usize = 10;
 
 #[derive(Debug, PartialEq, Clone, Deserialize)]
 pub struct Stats {
-    average_agent_balances: HashMap<String, i64>,
-    average_bet_win_percentage: HashMap<String, HashMap<BetHash, f64>>,
-    average_bet_income: HashMap<String, HashMap<BetHash, i64>>,
-    longest_loss_streak_pet_bet: HashMap<String, HashMap<BetHash, i64>>,
+    pub(super) average_agent_balances: HashMap<String, i64>,
+    pub(super) agent_balance_distributions: HashMap<String, Distribution>,
+    pub(super) average_bet_win_percentage: HashMap<String, HashMap<BetHash, f64>>,
+    pub(super) average_bet_income: HashMap<String, HashMap<BetHash, i64>>,
+    pub(super) bet_income_distributions: HashMap<String, HashMap<BetHash, Distribution>>,
+    pub(super) longest_loss_streak_pet_bet: HashMap<String, HashMap<BetHash, i64>>,
+    pub(super) bet_house_edge: HashMap<String, HashMap<BetHash, f64>>,
+    pub(super) theoretical_bet_house_edge: HashMap<String, HashMap<BetHash, f64>>,
+    pub(super) bet_house_edge_z_scores: HashMap<String, HashMap<BetHash, f64>>,
+    /// Average rounds per game a trigger placed the bet, for triggered bets only
+    pub(super) trigger_fires: HashMap<String, HashMap<BetHash, f64>>,
+    pub(super) triggered_win_rates: HashMap<String, HashMap<BetHash, f64>>,
+    pub(super) exit_reason_shares: HashMap<String, HashMap<ExitReason, f64>>,
+    pub(super) table_limit_hits: HashMap<String, HashMap<BetHash, i64>>,
+    pub(super) ruin_stats: HashMap<String, RuinStats>,
+    pub(super) balance_trajectories: HashMap<String, BalanceTrajectory>,
+    /// Average over the games of what each agent's bets cost per spin
+    pub(super) agent_expectations: HashMap<String, AgentExpectation>,
+    /// Paired differences between the agents, only when they played the same spins
+    #[serde(default)]
+    pub(super) agent_comparisons: Vec<AgentComparison>,
+    pub(super) roulette_type_stats: HashMap<RouletteType, Stats>,
+    pub(super) even_money_rule_stats: HashMap<EvenMoneyRule, Stats>,
+    /// Names of the bet types in the output, the aggregation keys stay classic
+    #[serde(default)]
+    pub(super) naming_scheme: NamingScheme,
 }
 impl serde::Serialize for Stats {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut map = serializer.serialize_map(Some(2))?;
+        let mut map = serializer.serialize_map(None)?;
         let serialized_bet_statistics: HashMap<BetHash, SerializedBetStats> = self
             .average_bet_win_percentage
             .iter()
-            .map(|(agent_name, bet_win_percentages)| {
+            .flat_map(|(agent_name, bet_win_percentages)| {
                 bet_win_percentages
                     .iter()
                     .map(|(bet_hash, win_percentage)| {
@@ -30,22 +61,55 @@ impl serde::Serialize for Stats {
                             bet_hash.clone(),
                             SerializedBetStats {
                                 agent_name: agent_name.clone(),
-                                bet_type: bet_hash.bet_type.clone(),
+                                bet_type: self.get_bet_type(bet_hash),
                                 bet_value: bet_hash.bet_value.clone(),
                                 progression_factor: bet_hash.progression_factor,
+                                strategy: bet_hash.strategy.clone(),
+                                trigger: bet_hash.trigger.clone(),
                                 win_percentage: *win_percentage,
                                 average_bet_income: self.average_bet_income[agent_name][bet_hash],
+                                income_distribution: self.bet_income_distributions[agent_name]
+                                    .get(bet_hash)
+                                    .cloned(),
                                 longest_loss_streak: self.longest_loss_streak_pet_bet[agent_name]
                                     [bet_hash],
+                                house_edge: self.bet_house_edge[agent_name][bet_hash],
+                                theoretical_house_edge: self.theoretical_bet_house_edge[agent_name]
+                                    [bet_hash],
+                                house_edge_z_score: self.bet_house_edge_z_scores[agent_name]
+                                    [bet_hash],
+                                table_limit_hits: self.table_limit_hits[agent_name][bet_hash],
+                                trigger_fires_per_game: self.trigger_fires[agent_name]
+                                    .get(bet_hash)
+                                    .copied(),
+                                triggered_win_rate: self.triggered_win_rates[agent_name]
+                                    .get(bet_hash)
+                                    .copied(),
                             },
                         )
                     })
                     .collect::<HashMap<BetHash, SerializedBetStats>>()
             })
-            .flatten()
             .collect();
         map.serialize_entry("average_agent_balances", &self.average_agent_balances)?;
+        map.serialize_entry(
+            "agent_balance_distributions",
+            &self.agent_balance_distributions,
+        )?;
         map.serialize_entry("bet_statistics", &serialized_bet_statistics)?;
+        map.serialize_entry("exit_reason_shares", &self.exit_reason_shares)?;
+        map.serialize_entry("ruin_statistics", &self.ruin_stats)?;
+        map.serialize_entry("balance_trajectories", &self.balance_trajectories)?;
+        map.serialize_entry("expected_loss_per_spin", &self.agent_expectations)?;
+        if !self.agent_comparisons.is_empty() {
+            map.serialize_entry("agent_comparisons", &self.agent_comparisons)?;
+        }
+        if !self.roulette_type_stats.is_empty() {
+            map.serialize_entry("roulette_type_statistics", &self.roulette_type_stats)?;
+        }
+        if !self.even_money_rule_stats.is_empty() {
+            map.serialize_entry("even_money_rule_statistics", &self.even_money_rule_stats)?;
+        }
         map.end()
     }
 }
@@ -68,6 +132,16 @@ impl fmt::Display for Stats {
                 ]));
             });
         output.push_str(&table.to_string());
+        output.push_str("\n\nFinal Balance Distribution:\n");
+        table = Table::new();
+        table.add_row(Self::distribution_header(&["AGENT"]));
+        self.agent_balance_distributions
+            .iter()
+            .sorted_by_key(|(agent_name, _)| agent_name.to_string())
+            .for_each(|(agent_name, distribution)| {
+                table.add_row(Self::distribution_row(&[agent_name], distribution));
+            });
+        output.push_str(&table.to_string());
         output.push_str("\n\nAverage Bet Win Percentages:\n");
         table = Table::new();
         table.add_row(Row::new(vec![
@@ -84,7 +158,7 @@ impl fmt::Display for Stats {
                     .for_each(|(bet_hash, win_percentage)| {
                         table.add_row(Row::new(vec![
                             Cell::new(agent_name),
-                            Cell::new(bet_hash.bet_type.as_str()),
+                            Cell::new(self.get_bet_type(bet_hash).as_str()),
                             Cell::new(bet_hash.bet_value.as_str()),
                             Cell::new(Self::format_as_percentage(*win_percentage).as_str()),
                         ]));
@@ -105,13 +179,37 @@ impl fmt::Display for Stats {
                 bet_incomes.iter().for_each(|(bet_hash, income)| {
                     table.add_row(Row::new(vec![
                         Cell::new(agent_name),
-                        Cell::new(bet_hash.bet_type.as_str()),
+                        Cell::new(self.get_bet_type(bet_hash).as_str()),
                         Cell::new(bet_hash.bet_value.as_str()),
                         Cell::new(Self::format_as_dollars_cents(*income).as_str()),
                     ]));
                 });
             });
         output.push_str(&table.to_string());
+        output.push_str("\n\nBet Income Distribution:\n");
+        table = Table::new();
+        table.add_row(Self::distribution_header(&[
+            "AGENT",
+            "BET TYPE",
+            "BET VALUE",
+        ]));
+        self.bet_income_distributions
+            .iter()
+            .for_each(|(agent_name, bet_distributions)| {
+                bet_distributions
+                    .iter()
+                    .for_each(|(bet_hash, distribution)| {
+                        table.add_row(Self::distribution_row(
+                            &[
+                                agent_name,
+                                &self.get_bet_type(bet_hash),
+                                &bet_hash.bet_value,
+                            ],
+                            distribution,
+                        ));
+                    });
+            });
+        output.push_str(&table.to_string());
         output.push_str("\n\nLongest Loss Streak Per Bet:\n");
         table = Table::new();
         table.add_row(Row::new(vec![
@@ -127,7 +225,7 @@ impl fmt::Display for Stats {
                     .for_each(|(bet_hash, longest_loss_streak)| {
                         table.add_row(Row::new(vec![
                             Cell::new(agent_name),
-                            Cell::new(bet_hash.bet_type.as_str()),
+                            Cell::new(self.get_bet_type(bet_hash).as_str()),
                             Cell::new(bet_hash.bet_value.as_str()),
                             Cell::new(longest_loss_streak.to_string().as_str()),
                         ]));
@@ -135,147 +233,352 @@ impl fmt::Display for Stats {
             },
         );
         output.push_str(&table.to_string());
+        output.push_str("\n\nObserved House Edge Per Bet:\n");
+        table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("AGENT"),
+            Cell::new("BET TYPE"),
+            Cell::new("BET VALUE"),
+            Cell::new("HOUSE EDGE"),
+            Cell::new("THEORETICAL"),
+            Cell::new("Z-SCORE"),
+        ]));
+        self.bet_house_edge
+            .iter()
+            .for_each(|(agent_name, bet_house_edges)| {
+                bet_house_edges.iter().for_each(|(bet_hash, house_edge)| {
+                    table.add_row(Row::new(vec![
+                        Cell::new(agent_name),
+                        Cell::new(self.get_bet_type(bet_hash).as_str()),
+                        Cell::new(bet_hash.bet_value.as_str()),
+                        Cell::new(Self::format_as_percentage(*house_edge).as_str()),
+                        Cell::new(
+                            Self::format_as_percentage(
+                                self.theoretical_bet_house_edge[agent_name][bet_hash],
+                            )
+                            .as_str(),
+                        ),
+                        Cell::new(
+                            format!("{:.2}", self.bet_house_edge_z_scores[agent_name][bet_hash])
+                                .as_str(),
+                        ),
+                    ]));
+                });
+            });
+        output.push_str(&table.to_string());
+        output.push_str("\n\nExpected Loss Per Spin:\n");
+        table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("AGENT"),
+            Cell::new("STAKED PER SPIN"),
+            Cell::new("EXPECTED LOSS PER SPIN"),
+            Cell::new("HOUSE EDGE"),
+        ]));
+        self.agent_expectations
+            .iter()
+            .sorted_by_key(|(agent_name, _)| agent_name.to_string())
+            .for_each(|(agent_name, agent_expectation)| {
+                table.add_row(Row::new(vec![
+                    Cell::new(agent_name),
+                    Cell::new(&Self::format_as_dollars_cents(
+                        agent_expectation.staked_cents_per_spin,
+                    )),
+                    Cell::new(&format!(
+                        "${:.4}",
+                        agent_expectation.expected_loss_cents_per_spin / 100.0
+                    )),
+                    Cell::new(&Self::format_as_percentage(
+                        agent_expectation.get_house_edge(),
+                    )),
+                ]));
+            });
+        output.push_str(&table.to_string());
+        output.push_str("\n\nTable Limit Hits Per Bet:\n");
+        table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("AGENT"),
+            Cell::new("BET TYPE"),
+            Cell::new("BET VALUE"),
+            Cell::new("TABLE LIMIT HITS"),
+        ]));
+        self.table_limit_hits
+            .iter()
+            .for_each(|(agent_name, bet_table_limit_hits)| {
+                bet_table_limit_hits
+                    .iter()
+                    .for_each(|(bet_hash, table_limit_hits)| {
+                        table.add_row(Row::new(vec![
+                            Cell::new(agent_name),
+                            Cell::new(self.get_bet_type(bet_hash).as_str()),
+                            Cell::new(bet_hash.bet_value.as_str()),
+                            Cell::new(table_limit_hits.to_string().as_str()),
+                        ]));
+                    });
+            });
+        output.push_str(&table.to_string());
+        if self.trigger_fires.values().any(|fires| !fires.is_empty()) {
+            output.push_str("\n\nBet Triggers:\n");
+            table = Table::new();
+            table.add_row(Row::new(vec![
+                Cell::new("AGENT"),
+                Cell::new("BET TYPE"),
+                Cell::new("BET VALUE"),
+                Cell::new("TRIGGER"),
+                Cell::new("FIRES PER GAME"),
+                Cell::new("WIN RATE WHEN FIRED"),
+            ]));
+            self.trigger_fires
+                .iter()
+                .sorted_by_key(|(agent_name, _)| agent_name.to_string())
+                .for_each(|(agent_name, trigger_fires)| {
+                    trigger_fires.iter().for_each(|(bet_hash, fires)| {
+                        table.add_row(Row::new(vec![
+                            Cell::new(agent_name),
+                            Cell::new(self.get_bet_type(bet_hash).as_str()),
+                            Cell::new(bet_hash.bet_value.as_str()),
+                            Cell::new(bet_hash.trigger.as_deref().unwrap_or_default()),
+                            Cell::new(format!("{:.2}", fires).as_str()),
+                            Cell::new(
+                                Self::format_as_percentage(
+                                    self.triggered_win_rates[agent_name][bet_hash],
+                                )
+                                .as_str(),
+                            ),
+                        ]));
+                    });
+                });
+            output.push_str(&table.to_string());
+        }
+        if !self.agent_comparisons.is_empty() {
+            output.push_str("\n\nPaired Agent Comparisons:\n");
+            table = Table::new();
+            table.add_row(Row::new(vec![
+                Cell::new("BASELINE"),
+                Cell::new("CHALLENGER"),
+                Cell::new("MEAN DIFFERENCE"),
+                Cell::new("PAIRED STD ERROR"),
+                Cell::new("UNPAIRED STD ERROR"),
+                Cell::new("VARIANCE REDUCTION"),
+                Cell::new("T"),
+                Cell::new("P-VALUE"),
+            ]));
+            self.agent_comparisons.iter().for_each(|comparison| {
+                table.add_row(Row::new(vec![
+                    Cell::new(&comparison.baseline),
+                    Cell::new(&comparison.challenger),
+                    Cell::new(&format!("${:.2}", comparison.mean_difference_cents / 100.0)),
+                    Cell::new(&format!(
+                        "${:.2}",
+                        comparison.paired_standard_error_cents / 100.0
+                    )),
+                    Cell::new(&format!(
+                        "${:.2}",
+                        comparison.unpaired_standard_error_cents / 100.0
+                    )),
+                    Cell::new(&Self::format_as_percentage(comparison.variance_reduction)),
+                    Cell::new(&format!("{:.2}", comparison.t_statistic)),
+                    Cell::new(&format!("{:.4}", comparison.p_value)),
+                ]));
+            });
+            output.push_str(&table.to_string());
+        }
+        output.push_str("\n\nSession Exit Reasons:\n");
+        table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("AGENT"),
+            Cell::new("EXIT REASON"),
+            Cell::new("SHARE OF SESSIONS"),
+        ]));
+        self.exit_reason_shares
+            .iter()
+            .for_each(|(agent_name, exit_reason_shares)| {
+                exit_reason_shares
+                    .iter()
+                    .sorted_by_key(|(exit_reason, _)| exit_reason.to_string())
+                    .for_each(|(exit_reason, share)| {
+                        table.add_row(Row::new(vec![
+                            Cell::new(agent_name),
+                            Cell::new(exit_reason.to_string().as_str()),
+                            Cell::new(Self::format_as_percentage(*share).as_str()),
+                        ]));
+                    });
+            });
+        output.push_str(&table.to_string());
+        output.push_str("\n\nRisk Of Ruin:\n");
+        table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("AGENT"),
+            Cell::new("RUINED GAMES"),
+            Cell::new("MEDIAN RUIN ROUND"),
+            Cell::new("EARLIEST RUIN ROUND"),
+            Cell::new("MEDIAN MAX DRAWDOWN"),
+            Cell::new("WORST MAX DRAWDOWN"),
+        ]));
+        self.ruin_stats
+            .iter()
+            .sorted_by_key(|(agent_name, _)| agent_name.to_string())
+            .for_each(|(agent_name, ruin_stats)| {
+                let ruin_rounds = ruin_stats.ruin_rounds.as_ref();
+                let max_drawdowns = ruin_stats.max_drawdowns.as_ref();
+                table.add_row(Row::new(vec![
+                    Cell::new(agent_name),
+                    Cell::new(&Self::format_as_percentage(ruin_stats.ruin_share)),
+                    Cell::new(&Self::format_option(ruin_rounds.map(|d| d.median))),
+                    Cell::new(&Self::format_option(ruin_rounds.map(|d| d.min))),
+                    Cell::new(&Self::format_option(
+                        max_drawdowns.map(|d| Self::format_cents(d.median)),
+                    )),
+                    Cell::new(&Self::format_option(
+                        max_drawdowns.map(|d| Self::format_as_dollars_cents(d.max)),
+                    )),
+                ]));
+            });
+        output.push_str(&table.to_string());
+        output.push_str("\n\nSurvival Curve (share of games still solvent):\n");
+        table = Table::new();
+        let checkpoints = self.survival_checkpoints();
+        let mut header = vec![Cell::new("AGENT")];
+        for round_number in checkpoints.iter() {
+            header.push(Cell::new(&format!("ROUND {}", round_number)));
+        }
+        table.add_row(Row::new(header));
+        self.ruin_stats
+            .iter()
+            .sorted_by_key(|(agent_name, _)| agent_name.to_string())
+            .for_each(|(agent_name, ruin_stats)| {
+                let mut cells = vec![Cell::new(agent_name)];
+                for round_number in checkpoints.iter() {
+                    cells.push(Cell::new(&Self::format_as_percentage(
+                        ruin_stats.get_survival(*round_number),
+                    )));
+                }
+                table.add_row(Row::new(cells));
+            });
+        output.push_str(&table.to_string());
+        self.roulette_type_stats
+            .iter()
+            .sorted_by_key(|(roulette_type, _)| roulette_type.to_string())
+            .for_each(|(roulette_type, stats)| {
+                output.push_str(&format!("\n\n{} Roulette:\n", roulette_type));
+                output.push_str(&stats.to_string());
+            });
+        self.even_money_rule_stats
+            .iter()
+            .sorted_by_key(|(even_money_rule, _)| even_money_rule.to_string())
+            .for_each(|(even_money_rule, stats)| {
+                output.push_str(&format!("\n\n{} Even-Money Rule:\n", even_money_rule));
+                output.push_str(&stats.to_string());
+            });
         return write!(f, "{}", output);
     }
 }
 
 impl Stats {
-    pub fn from_games(games: &Vec<RouletteGame>) -> Self {
-        return Stats {
-            average_agent_balances: Self::gen_average_agent_balances(games),
-            average_bet_win_percentage: Self::gen_bet_win_percentages(games),
-            average_bet_income: Self::gen_average_bet_income(games),
-            longest_loss_streak_pet_bet: Self::gen_longest_loss_streak_pet_bet(games),
-        };
+    /// Names the bet types of these stats and of their breakdowns after `naming_scheme`
+    pub fn set_naming_scheme(&mut self, naming_scheme: NamingScheme) {
+        self.naming_scheme = naming_scheme;
+        self.roulette_type_stats
+            .values_mut()
+            .chain(self.even_money_rule_stats.values_mut())
+            .for_each(|stats| stats.set_naming_scheme(naming_scheme));
     }
 
-    fn gen_average_agent_balances(games: &Vec<RouletteGame>) -> HashMap<String, i64> {
-        let mut average_agent_balances: HashMap<String, i64> = HashMap::new();
-        let number_of_games = games.len() as i32;
+    fn get_bet_type(&self, bet_hash: &BetHash) -> String {
+        return self.naming_scheme.rename_bet_type(&bet_hash.bet_type);
+    }
 
-        for game in games.iter() {
-            for agent in game.agents.iter() {
-                average_agent_balances
-                    .entry(agent.name.clone())
-                    .and_modify(|balance| *balance += agent.balance_cents)
-                    .or_insert(agent.balance_cents);
-            }
-        }
+    /// Balance trajectory of every agent, cut down to at most `number_of_points` rounds
+    pub fn get_balance_trajectories(
+        &self,
+        number_of_points: Option<usize>,
+    ) -> HashMap<String, BalanceTrajectory> {
+        return self
+            .balance_trajectories
+            .iter()
+            .map(|(agent_name, trajectory)| {
+                let trajectory = match number_of_points {
+                    Some(number_of_points) => trajectory.downsample(number_of_points),
+                    None => trajectory.clone(),
+                };
+                (agent_name.clone(), trajectory)
+            })
+            .collect();
+    }
 
-        average_agent_balances.iter_mut().for_each(|(_, balance)| {
-            *balance /= number_of_games as i64;
-        });
+    /// Stats of games kept in memory, runs fold theirs in as they finish instead
+    #[cfg(test)]
+    pub fn from_games(games: &[super::roulette_game::RouletteGame]) -> Self {
+        let mut stats_accumulator = super::stats_accumulator::StatsAccumulator::default();
+        games
+            .iter()
+            .for_each(|game| stats_accumulator.add_game(game));
+        return stats_accumulator.to_stats();
+    }
 
-        return average_agent_balances;
-    }
-
-    fn gen_bet_win_percentages(
-        games: &Vec<RouletteGame>,
-    ) -> HashMap<String, HashMap<BetHash, f64>> {
-        let mut average_bet_win_percentage: HashMap<String, HashMap<BetHash, f64>> = HashMap::new();
-        let number_of_games = games.len() as i32;
-
-        for game in games.iter() {
-            for agent in game.agents.iter() {
-                for bet in agent.strategic_bets.iter() {
-                    let number_of_wins = bet
-                        .bet_logs
-                        .iter()
-                        .filter(|bet_log| bet_log.bet_state == BetState::Won)
-                        .count();
-                    let number_of_rounds = bet.bet_logs.len();
-                    let win_percentage: f64 = number_of_wins as f64 / number_of_rounds as f64;
-                    *average_bet_win_percentage
-                        .entry(agent.name.clone())
-                        .or_insert(HashMap::new())
-                        .entry(bet.into())
-                        .or_insert(0.0) += win_percentage;
-                }
-            }
+    fn distribution_header(labels: &[&str]) -> Row {
+        let mut cells: Vec<Cell> = labels.iter().map(|label| Cell::new(label)).collect();
+        for label in [
+            "MIN",
+            "P5",
+            "P25",
+            "MEDIAN",
+            "P75",
+            "P95",
+            "MAX",
+            "STD DEV",
+            "95% CI OF MEAN",
+        ] {
+            cells.push(Cell::new(label));
         }
-
-        average_bet_win_percentage
-            .iter_mut()
-            .for_each(|(_, bet_win_percentages)| {
-                bet_win_percentages
-                    .iter_mut()
-                    .for_each(|(_, win_percentage_sum)| {
-                        *win_percentage_sum /= number_of_games as f64;
-                    });
-            });
-
-        return average_bet_win_percentage;
+        return Row::new(cells);
     }
 
-    fn gen_average_bet_income(games: &Vec<RouletteGame>) -> HashMap<String, HashMap<BetHash, i64>> {
-        let mut average_bet_income: HashMap<String, HashMap<BetHash, i64>> = HashMap::new();
-        let number_of_games = games.len() as i32;
-
-        for game in games.iter() {
-            for agent in game.agents.iter() {
-                for bet in agent.strategic_bets.iter() {
-                    let mut income_per_bet: i64 = 0;
-                    bet.bet_logs.iter().for_each(|bet_log| {
-                        if bet_log.bet_state == BetState::Won {
-                            income_per_bet += bet_log.amount_cents;
-                        } else if bet_log.bet_state == BetState::Lost {
-                            income_per_bet -= bet_log.amount_cents;
-                        }
-                    });
-                    *average_bet_income
-                        .entry(agent.name.clone())
-                        .or_insert(HashMap::new())
-                        .entry(bet.into())
-                        .or_insert(0) += income_per_bet;
-                }
-            }
+    fn distribution_row(labels: &[&str], distribution: &Distribution) -> Row {
+        let mut cells: Vec<Cell> = labels.iter().map(|label| Cell::new(label)).collect();
+        let (lower, upper) = distribution.mean_confidence_interval_95;
+        for value in [
+            Self::format_as_dollars_cents(distribution.min),
+            Self::format_cents(distribution.p5),
+            Self::format_cents(distribution.p25),
+            Self::format_cents(distribution.median),
+            Self::format_cents(distribution.p75),
+            Self::format_cents(distribution.p95),
+            Self::format_as_dollars_cents(distribution.max),
+            Self::format_cents(distribution.std_dev),
+            format!(
+                "{} - {}",
+                Self::format_cents(lower),
+                Self::format_cents(upper)
+            ),
+        ] {
+            cells.push(Cell::new(&value));
         }
+        return Row::new(cells);
+    }
 
-        average_bet_income.iter_mut().for_each(|(_, bet_incomes)| {
-            bet_incomes.iter_mut().for_each(|(_, income_sum)| {
-                *income_sum /= number_of_games as i64;
-            });
-        });
+    /// Rounds at which the survival curve is printed, spread evenly over the longest game
+    fn survival_checkpoints(&self) -> Vec<usize> {
+        let number_of_rounds = self
+            .ruin_stats
+            .values()
+            .map(|ruin_stats| ruin_stats.survival_curve.len())
+            .max()
+            .unwrap_or(0);
+        return (1..=SURVIVAL_CHECKPOINTS)
+            .map(|checkpoint| checkpoint * number_of_rounds / SURVIVAL_CHECKPOINTS)
+            .filter(|round_number| *round_number > 0)
+            .unique()
+            .collect();
+    }
 
-        return average_bet_income;
-    }
-
-    fn gen_longest_loss_streak_pet_bet(
-        games: &Vec<RouletteGame>,
-    ) -> HashMap<String, HashMap<BetHash, i64>> {
-        let mut longest_loss_streak_pet_bet: HashMap<String, HashMap<BetHash, i64>> =
-            HashMap::new();
-
-        for game in games.iter() {
-            for agent in game.agents.iter() {
-                for bet in agent.strategic_bets.iter() {
-                    let mut longest_loss_streak = 0;
-                    let mut curr_loss_streak = 0;
-                    for bet_log in bet.bet_logs.iter() {
-                        if bet_log.bet_state == BetState::Lost {
-                            curr_loss_streak += 1;
-                        } else {
-                            curr_loss_streak = 0;
-                        }
-                        if curr_loss_streak > longest_loss_streak {
-                            longest_loss_streak = curr_loss_streak;
-                        }
-                    }
-                    let _ = *longest_loss_streak_pet_bet
-                        .entry(agent.name.clone())
-                        .or_insert(HashMap::new())
-                        .entry(BetHash::from(bet))
-                        .and_modify(|existing_streak| {
-                            if longest_loss_streak > *existing_streak {
-                                *existing_streak = longest_loss_streak;
-                            }
-                        })
-                        .or_insert(longest_loss_streak);
-                }
-            }
-        }
+    fn format_option<T: ToString>(value: Option<T>) -> String {
+        return value
+            .map(|value| value.to_string())
+            .unwrap_or("-".to_string());
+    }
 
-        return longest_loss_streak_pet_bet;
+    fn format_cents(value: f64) -> String {
+        return Self::format_as_dollars_cents(value.round() as i64);
     }
 
     fn format_as_percentage(value: f64) -> String {
@@ -295,11 +598,18 @@ impl Stats {
 }
 
 #[derive(Debug, PartialEq, Hash, Clone, Deserialize, Eq)]
-struct BetHash {
+pub(super) struct BetHash {
     bet_type: String,
     bet_value: String,
     initial_amount_cents: i64,
     progression_factor: i64,
+    strategy: String,
+    trigger: Option<String>,
+}
+impl BetHash {
+    pub(super) fn has_trigger(&self) -> bool {
+        return self.trigger.is_some();
+    }
 }
 impl From<Bet> for BetHash {
     fn from(bet: Bet) -> Self {
@@ -313,6 +623,8 @@ impl From<&Bet> for BetHash {
             bet_value: bet.bet_value.get_value_string(),
             initial_amount_cents: bet.initial_amount_cents,
             progression_factor: bet.progression_factor,
+            strategy: bet.strategy.get_name(),
+            trigger: bet.trigger.as_ref().map(|trigger| trigger.to_string()),
         }
     }
 }
@@ -321,10 +633,17 @@ impl Serialize for BetHash {
     where
         S: Serializer,
     {
-        let key = format!(
-            "{}_{}_{}_{}",
-            self.bet_type, self.bet_value, self.initial_amount_cents, self.progression_factor
+        let mut key = format!(
+            "{}_{}_{}_{}_{}",
+            self.bet_type,
+            self.bet_value,
+            self.initial_amount_cents,
+            self.progression_factor,
+            self.strategy
         );
+        if let Some(trigger) = &self.trigger {
+            key.push_str(&format!("_{}", trigger));
+        }
         serializer.serialize_str(&key)
     }
 }
@@ -335,29 +654,47 @@ struct SerializedBetStats {
     bet_type: String,
     bet_value: String,
     progression_factor: i64,
+    strategy: String,
+    trigger: Option<String>,
     win_percentage: f64,
     average_bet_income: i64,
+    income_distribution: Option<Distribution>,
     longest_loss_streak: i64,
+    house_edge: f64,
+    theoretical_house_edge: f64,
+    house_edge_z_score: f64,
+    table_limit_hits: i64,
+    trigger_fires_per_game: Option<f64>,
+    triggered_win_rate: Option<f64>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::agent::agent::Agent;
+    use crate::agent::agent_exit::AgentExit;
     use crate::agent::agent_log::AgentLog;
     use crate::bet::bet_log::BetLog;
+    use crate::bet::bet_state::BetState;
+    use crate::bet::bet_trigger::BetTrigger;
     use crate::bet::bet_value::BetValue;
     use crate::board::board::Board;
     use crate::board::slot::Slot;
+    use crate::board::wheel::Wheel;
+    use crate::roulette::even_money_rule::EvenMoneyRule;
     use crate::roulette::game_logs::GameLog;
+    use crate::roulette::payout_table::PayoutTable;
     use crate::roulette::roulette_game::RouletteGame;
     use crate::roulette::roulette_type::RouletteType;
+    use crate::roulette::stats_accumulator::StatsAccumulator;
+    use crate::roulette::table_limits::TableLimits;
+    use crate::strategy::strategy_type::StrategyType;
     use crate::types::{
         color::Color, column::Column, dozen::Dozen, even_odd::EvenOdd, half::Half, row::Row,
     };
 
-    const AGENT_1_NAME: &'static str = "BOB";
-    const AGENT_2_NAME: &'static str = "ALICE";
+    const AGENT_1_NAME: &str = "BOB";
+    const AGENT_2_NAME: &str = "ALICE";
 
     fn create_testing_board() -> Board {
         let mut slots = Vec::new();
@@ -385,7 +722,10 @@ mod tests {
                 column: Column::try_from(i).unwrap(),
             });
         }
-        let board = Board { slots };
+        let board = Board {
+            slots,
+            wheel: Wheel::new(&RouletteType::European),
+        };
         return board;
     }
 
@@ -402,31 +742,45 @@ mod tests {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 1,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Won,
                     amount_cents: 2000,
                     round_number: 2,
+                    payout_cents: 4000,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Won,
                     amount_cents: 1000,
                     round_number: 3,
+                    payout_cents: 2000,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 4,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Lost,
                     amount_cents: 2000,
                     round_number: 5,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
             ],
             bet_value: BetValue::Color(Color::Red),
             initial_amount_cents: 1000,
+            payout_cents: 0,
             progression_factor: 2,
+            strategy: StrategyType::default(),
+            trigger: None,
+            table_limit_hit: false,
             bet_state: BetState::Active,
         };
         let agent1_bet2 = Bet {
@@ -436,31 +790,45 @@ mod tests {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 1,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 2,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 3,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Won,
                     amount_cents: 36000,
                     round_number: 4,
+                    payout_cents: 72000,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 5,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
             ],
             bet_value: BetValue::Number(23),
             initial_amount_cents: 1000,
+            payout_cents: 0,
             progression_factor: 1,
+            strategy: StrategyType::default(),
+            trigger: None,
+            table_limit_hit: false,
             bet_state: BetState::Active,
         };
 
@@ -471,31 +839,45 @@ mod tests {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 1,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Won,
                     amount_cents: 2000,
                     round_number: 2,
+                    payout_cents: 4000,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Won,
                     amount_cents: 1000,
                     round_number: 3,
+                    payout_cents: 2000,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Lost,
                     amount_cents: 1000,
                     round_number: 4,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
                 BetLog {
                     bet_state: BetState::Lost,
                     amount_cents: 2000,
                     round_number: 5,
+                    payout_cents: 0,
+                    table_limit_hit: false,
                 },
             ],
             bet_value: BetValue::Dozen(Dozen::One),
             initial_amount_cents: 1000,
+            payout_cents: 0,
             progression_factor: 2,
+            strategy: StrategyType::default(),
+            trigger: None,
+            table_limit_hit: false,
             bet_state: BetState::Active,
         };
 
@@ -511,24 +893,35 @@ mod tests {
                         AgentLog {
                             round_number: 1,
                             balance_cents: 98000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 2,
                             balance_cents: 99000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 3,
                             balance_cents: 99000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 4,
                             balance_cents: 134000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 5,
                             balance_cents: 131000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                     ],
+                    ..Default::default()
                 },
                 Agent {
                     name: AGENT_2_NAME.to_string(),
@@ -538,24 +931,35 @@ mod tests {
                         AgentLog {
                             round_number: 1,
                             balance_cents: 99000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 2,
                             balance_cents: 101000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 3,
                             balance_cents: 102000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 4,
                             balance_cents: 101000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                         AgentLog {
                             round_number: 5,
                             balance_cents: 99000,
+                            exit_reason: None,
+                            out_of_funds: false,
                         },
                     ],
+                    ..Default::default()
                 },
             ],
             number_of_rounds: 10,
@@ -583,6 +987,9 @@ mod tests {
                 },
             ],
             roulette_type: RouletteType::European,
+            even_money_rule: EvenMoneyRule::Standard,
+            table_limits: TableLimits::default(),
+            payout_table: PayoutTable::default(),
         };
         return vec![game];
     }
@@ -607,17 +1014,17 @@ mod tests {
             AGENT_2_NAME
         );
         assert_eq!(
-            stats.average_bet_win_percentage[AGENT_1_NAME][agent1_bet1_hash], 0.4 as f64,
+            stats.average_bet_win_percentage[AGENT_1_NAME][agent1_bet1_hash], 0.4_f64,
             "{}-Bet1 average win percentage has not been calculated correctly",
             AGENT_1_NAME
         );
         assert_eq!(
-            stats.average_bet_win_percentage[AGENT_1_NAME][agent1_bet2_hash], 0.2 as f64,
+            stats.average_bet_win_percentage[AGENT_1_NAME][agent1_bet2_hash], 0.2_f64,
             "{}-Bet2 average win percentage has not been calculated correctly",
             AGENT_1_NAME
         );
         assert_eq!(
-            stats.average_bet_win_percentage[AGENT_2_NAME][agent2_bet1_hash], 0.4 as f64,
+            stats.average_bet_win_percentage[AGENT_2_NAME][agent2_bet1_hash], 0.4_f64,
             "{}-Bet1 average win percentage has not been calculated correctly",
             AGENT_2_NAME
         );
@@ -696,6 +1103,240 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_roulette_type_stats() {
+        let games = create_games();
+        let stats = Stats::from_games(&games);
+        assert!(stats.roulette_type_stats.is_empty());
+
+        let mut american_game = games[0].clone();
+        american_game.roulette_type = RouletteType::American;
+        american_game.agents[0].balance_cents = 91000;
+        // the breakdown only starts with the American game, after a European one
+        let mixed_games = vec![games[0].clone(), american_game, games[0].clone()];
+        let stats = Stats::from_games(&mixed_games);
+        assert_eq!(stats.roulette_type_stats.len(), 2);
+        assert_eq!(stats.average_agent_balances[AGENT_1_NAME], 117666);
+        assert_eq!(
+            stats.roulette_type_stats[&RouletteType::European].average_agent_balances[AGENT_1_NAME],
+            131000
+        );
+        assert_eq!(
+            stats.roulette_type_stats[&RouletteType::American].average_agent_balances[AGENT_1_NAME],
+            91000
+        );
+        let json = serde_json::to_value(&stats).unwrap();
+        assert!(json["roulette_type_statistics"]["American"].is_object());
+    }
+
+    #[test]
+    fn test_bet_house_edge() {
+        let games = create_games();
+        let stats = Stats::from_games(&games);
+        let agent1_bet1_hash = &BetHash::from(&games[0].agents[0].strategic_bets[0]);
+        let agent1_bet2_hash = &BetHash::from(&games[0].agents[0].strategic_bets[1]);
+        assert_eq!(
+            stats.bet_house_edge[AGENT_1_NAME][agent1_bet1_hash],
+            1000.0 / 7000.0
+        );
+        assert_eq!(
+            stats.bet_house_edge[AGENT_1_NAME][agent1_bet2_hash],
+            -32000.0 / 40000.0
+        );
+    }
+
+    #[test]
+    fn test_theoretical_house_edge() {
+        let games = create_games();
+        let stats = Stats::from_games(&games);
+        let agent1_bet1_hash = &BetHash::from(&games[0].agents[0].strategic_bets[0]);
+        let theoretical_house_edge =
+            stats.theoretical_bet_house_edge[AGENT_1_NAME][agent1_bet1_hash];
+        assert!((theoretical_house_edge - 1.0 / 37.0).abs() < 1e-12);
+        // the bet lost more than the payout table expects
+        assert!(stats.bet_house_edge_z_scores[AGENT_1_NAME][agent1_bet1_hash] < 0.0);
+
+        let agent_expectation = stats.agent_expectations[AGENT_1_NAME];
+        assert_eq!(agent_expectation.staked_cents_per_spin, 2000);
+        assert!((agent_expectation.expected_loss_cents_per_spin - 2000.0 / 37.0).abs() < 1e-9);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert!(json["expected_loss_per_spin"][AGENT_1_NAME].is_object());
+    }
+
+    #[test]
+    fn test_naming_scheme() {
+        let mut games = create_games();
+        games.iter_mut().for_each(|game| {
+            game.agents[0].strategic_bets[1].bet_value = BetValue::Row(Row::One);
+        });
+        let mut stats = Stats::from_games(&games);
+        assert!(stats.to_string().contains("Row"));
+        assert!(!stats.to_string().contains("Table Column"));
+
+        stats.set_naming_scheme(NamingScheme::Casino);
+        assert!(stats.to_string().contains("Table Column"));
+        let json = serde_json::to_value(&stats).unwrap();
+        let bet_types: Vec<&str> = json["bet_statistics"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|bet_stats| bet_stats["bet_type"].as_str().unwrap())
+            .collect();
+        assert!(bet_types.contains(&"Table Column"));
+        assert!(!bet_types.contains(&"Row"));
+    }
+
+    #[test]
+    fn test_even_money_rule_stats() {
+        let games = create_games();
+        let mut la_partage_game = games[0].clone();
+        la_partage_game.even_money_rule = EvenMoneyRule::LaPartage;
+        la_partage_game.agents[1].balance_cents = 101000;
+        let stats = Stats::from_games(&[games[0].clone(), la_partage_game]);
+        assert!(stats.roulette_type_stats.is_empty());
+        assert_eq!(stats.even_money_rule_stats.len(), 2);
+        assert_eq!(
+            stats.even_money_rule_stats[&EvenMoneyRule::LaPartage].average_agent_balances
+                [AGENT_2_NAME],
+            101000
+        );
+    }
+
+    #[test]
+    fn test_table_limit_hits() {
+        let mut games = create_games();
+        games[0].agents[0].strategic_bets[0].bet_logs[1].table_limit_hit = true;
+        let mut second_game = games[0].clone();
+        second_game.agents[0].strategic_bets[0].bet_logs[3].table_limit_hit = true;
+        games.push(second_game);
+        let stats = Stats::from_games(&games);
+        let agent1_bet1_hash = &BetHash::from(&games[0].agents[0].strategic_bets[0]);
+        let agent1_bet2_hash = &BetHash::from(&games[0].agents[0].strategic_bets[1]);
+        assert_eq!(stats.table_limit_hits[AGENT_1_NAME][agent1_bet1_hash], 3);
+        assert_eq!(stats.table_limit_hits[AGENT_1_NAME][agent1_bet2_hash], 0);
+    }
+
+    #[test]
+    fn test_trigger_stats() {
+        let mut games = create_games();
+        games[0].agents[0].strategic_bets[0].trigger = Some(BetTrigger::Sleeper {
+            of: BetValue::Color(Color::Black),
+            spins: 3,
+        });
+        let stats = Stats::from_games(&games);
+        let agent1_bet1_hash = &BetHash::from(&games[0].agents[0].strategic_bets[0]);
+        let agent1_bet2_hash = &BetHash::from(&games[0].agents[0].strategic_bets[1]);
+        assert_eq!(stats.trigger_fires[AGENT_1_NAME][agent1_bet1_hash], 5.0);
+        assert_eq!(
+            stats.triggered_win_rates[AGENT_1_NAME][agent1_bet1_hash],
+            0.4
+        );
+        assert!(!stats.trigger_fires[AGENT_1_NAME].contains_key(agent1_bet2_hash));
+        assert!(stats.trigger_fires[AGENT_2_NAME].is_empty());
+    }
+
+    #[test]
+    fn test_exit_reason_shares() {
+        let mut games = create_games();
+        let mut exited_game = games[0].clone();
+        exited_game.agents[1].exit = Some(AgentExit {
+            round_number: 3,
+            exit_reason: ExitReason::TakeProfit,
+        });
+        games.push(exited_game);
+        let stats = Stats::from_games(&games);
+        assert_eq!(
+            stats.exit_reason_shares[AGENT_1_NAME],
+            HashMap::from([(ExitReason::Completed, 1.0)])
+        );
+        assert_eq!(
+            stats.exit_reason_shares[AGENT_2_NAME],
+            HashMap::from([(ExitReason::Completed, 0.5), (ExitReason::TakeProfit, 0.5)])
+        );
+    }
+
+    #[test]
+    fn test_distributions() {
+        let mut games = create_games();
+        let mut second_game = games[0].clone();
+        second_game.agents[0].balance_cents = 91000;
+        second_game.agents[0].strategic_bets[1].bet_logs[3].bet_state = BetState::Lost;
+        second_game.agents[0].strategic_bets[1].bet_logs[3].payout_cents = 0;
+        games.push(second_game);
+        let stats = Stats::from_games(&games);
+
+        let balances = &stats.agent_balance_distributions[AGENT_1_NAME];
+        assert_eq!(balances.count, 2);
+        assert_eq!((balances.min, balances.max), (91000, 131000));
+        assert_eq!(balances.median, 111000.0);
+        assert_eq!(balances.p5, 93000.0);
+        assert_eq!(balances.std_dev, 800000000.0_f64.sqrt());
+        let agent1_bet2_hash = &BetHash::from(&games[0].agents[0].strategic_bets[1]);
+        let incomes = &stats.bet_income_distributions[AGENT_1_NAME][agent1_bet2_hash];
+        assert_eq!((incomes.min, incomes.max), (-40000, 32000));
+        assert_eq!(incomes.mean, -4000.0);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(
+            json["agent_balance_distributions"][AGENT_2_NAME]["median"],
+            99000.0
+        );
+        assert!(stats.to_string().contains("Final Balance Distribution"));
+    }
+
+    #[test]
+    fn test_ruin_stats() {
+        let mut games = create_games();
+        let mut ruined_game = games[0].clone();
+        ruined_game.agents[1].agent_logs[3].out_of_funds = true;
+        games.push(ruined_game);
+        let stats = Stats::from_games(&games);
+
+        let ruin_stats = &stats.ruin_stats[AGENT_2_NAME];
+        assert_eq!(ruin_stats.ruin_share, 0.5);
+        assert_eq!(ruin_stats.ruin_rounds.as_ref().unwrap().min, 4);
+        assert_eq!(ruin_stats.survival_curve.len(), 10);
+        assert_eq!(ruin_stats.get_survival(3), 1.0);
+        assert_eq!(ruin_stats.get_survival(4), 0.5);
+        // 102000 peak down to 99000
+        assert_eq!(ruin_stats.max_drawdowns.as_ref().unwrap().max, 3000);
+        assert_eq!(stats.ruin_stats[AGENT_1_NAME].ruin_share, 0.0);
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["ruin_statistics"][AGENT_2_NAME]["ruin_share"], 0.5);
+    }
+
+    #[test]
+    fn test_balance_trajectories() {
+        let mut games = create_games();
+        let mut second_game = games[0].clone();
+        second_game.agents[0].agent_logs[4].balance_cents = 91000;
+        games.push(second_game);
+        let mut stats_accumulator = StatsAccumulator::new(2, false);
+        games
+            .iter()
+            .for_each(|game| stats_accumulator.add_game(game));
+        let stats = stats_accumulator.to_stats();
+
+        let trajectory = &stats.balance_trajectories[AGENT_1_NAME];
+        let round_numbers: Vec<i32> = trajectory
+            .points
+            .iter()
+            .map(|point| point.round_number)
+            .collect();
+        assert_eq!(round_numbers, vec![5, 10]);
+        assert_eq!(trajectory.points[0].mean, 111000.0);
+        assert_eq!(trajectory.points[0].p5, 93000.0);
+        // rounds past the logs keep the final balance
+        assert_eq!(trajectory.points[1].median, 131000.0);
+        assert_eq!(
+            stats.get_balance_trajectories(Some(1))[AGENT_1_NAME].points[0].round_number,
+            10
+        );
+        let json = serde_json::to_value(&stats).unwrap();
+        assert!(json["balance_trajectories"][AGENT_2_NAME]["points"].is_array());
+    }
+
     #[test]
     fn test_average_agent_balances() {
         let games = create_games();
@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{
+    agent_comparison::ComparisonTotals,
+    balance_trajectory::TrajectoryTotals,
+    distribution::DistributionTotals,
+    even_money_rule::EvenMoneyRule,
+    expected_value::{AgentExpectation, BetExpectation},
+    game_configs::DEFAULT_TRAJECTORY_POINTS,
+    roulette_game::RouletteGame,
+    roulette_type::RouletteType,
+    ruin_stats::RuinTotals,
+    stats::{BetHash, Stats},
+};
+use crate::agent::exit_reason::ExitReason;
+use crate::bet::{bet::Bet, bet_state::BetState, naming_scheme::NamingScheme};
+
+/// Running totals of one bet of an agent over the games added so far
+#[derive(Debug, PartialEq, Clone, Default)]
+struct BetTotals {
+    win_percentage_sum: f64,
+    income_cents: i64,
+    /// Income of the bet per game
+    game_incomes_cents: DistributionTotals,
+    staked_cents: i64,
+    longest_loss_streak: i64,
+    table_limit_hits: i64,
+    /// Income the payout table predicts for what was staked
+    expected_income_cents: f64,
+    /// Variance of the income around `expected_income_cents`
+    income_variance: f64,
+    /// Rounds a trigger let the bet onto the table
+    trigger_fires: i64,
+    /// Of the `trigger_fires`, the rounds the bet won
+    triggered_wins: i64,
+}
+
+impl BetTotals {
+    fn add_bet(&mut self, bet: &Bet, bet_expectation: &BetExpectation) {
+        let number_of_wins = bet
+            .bet_logs
+            .iter()
+            .filter(|bet_log| bet_log.bet_state == BetState::Won)
+            .count();
+        let number_of_rounds = bet
+            .bet_logs
+            .iter()
+            .filter(|bet_log| bet_log.bet_state != BetState::Retired)
+            .count();
+        // a bet whose trigger never fired has no rounds to its name
+        if number_of_rounds > 0 {
+            self.win_percentage_sum += number_of_wins as f64 / number_of_rounds as f64;
+        }
+
+        let mut curr_loss_streak = 0;
+        let mut game_income_cents = 0;
+        for bet_log in bet.bet_logs.iter() {
+            let staked_cents = bet_log.staked_cents();
+            game_income_cents += bet_log.income_cents();
+            self.staked_cents += staked_cents;
+            self.expected_income_cents += bet_expectation.expected_value * staked_cents as f64;
+            self.income_variance += bet_expectation.variance * (staked_cents * staked_cents) as f64;
+            if bet_log.table_limit_hit {
+                self.table_limit_hits += 1;
+            }
+            // rounds played on from En Prison don't stake anything
+            if bet.trigger.is_some() && staked_cents > 0 {
+                self.trigger_fires += 1;
+                if bet_log.bet_state == BetState::Won {
+                    self.triggered_wins += 1;
+                }
+            }
+            if bet_log.bet_state == BetState::Lost {
+                curr_loss_streak += 1;
+            } else {
+                curr_loss_streak = 0;
+            }
+            if curr_loss_streak > self.longest_loss_streak {
+                self.longest_loss_streak = curr_loss_streak;
+            }
+        }
+        self.income_cents += game_income_cents;
+        self.game_incomes_cents.add(game_income_cents);
+    }
+
+    fn house_edge(&self) -> f64 {
+        if self.staked_cents > 0 {
+            return -self.income_cents as f64 / self.staked_cents as f64;
+        }
+        return 0.0;
+    }
+
+    fn triggered_win_rate(&self) -> f64 {
+        if self.trigger_fires > 0 {
+            return self.triggered_wins as f64 / self.trigger_fires as f64;
+        }
+        return 0.0;
+    }
+
+    fn theoretical_house_edge(&self) -> f64 {
+        if self.staked_cents > 0 {
+            return -self.expected_income_cents / self.staked_cents as f64;
+        }
+        return 0.0;
+    }
+
+    /// Standard deviations the observed income lies away from the expected one
+    fn house_edge_z_score(&self) -> f64 {
+        if self.income_variance > 0.0 {
+            return (self.income_cents as f64 - self.expected_income_cents)
+                / self.income_variance.sqrt();
+        }
+        return 0.0;
+    }
+}
+
+/// Running totals of a set of games, enough to produce their `Stats`
+#[derive(Debug, PartialEq, Clone, Default)]
+struct GameTotals {
+    number_of_games: i64,
+    agent_balances_cents: HashMap<String, i64>,
+    final_agent_balances_cents: HashMap<String, DistributionTotals>,
+    bet_totals: HashMap<String, HashMap<BetHash, BetTotals>>,
+    exit_reason_counts: HashMap<String, HashMap<ExitReason, i64>>,
+    ruin_totals: HashMap<String, RuinTotals>,
+    trajectory_totals: HashMap<String, TrajectoryTotals>,
+    /// Summed over the games, averaged when turned into `Stats`
+    agent_expectations: HashMap<String, AgentExpectation>,
+}
+
+impl GameTotals {
+    fn add_game(&mut self, game: &RouletteGame, trajectory_points: usize) {
+        self.number_of_games += 1;
+        for agent in game.agents.iter() {
+            *self
+                .agent_balances_cents
+                .entry(agent.name.clone())
+                .or_insert(0) += agent.balance_cents;
+            self.final_agent_balances_cents
+                .entry(agent.name.clone())
+                .or_default()
+                .add(agent.balance_cents);
+            *self
+                .exit_reason_counts
+                .entry(agent.name.clone())
+                .or_default()
+                .entry(agent.exit_reason())
+                .or_insert(0) += 1;
+            self.ruin_totals
+                .entry(agent.name.clone())
+                .or_default()
+                .add_session(
+                    agent.ruin_round(),
+                    agent.max_drawdown_cents(),
+                    game.number_of_rounds,
+                );
+            self.trajectory_totals
+                .entry(agent.name.clone())
+                .or_insert_with(|| TrajectoryTotals::new(game.number_of_rounds, trajectory_points))
+                .add_session(|round_number| {
+                    agent
+                        .agent_logs
+                        .get(round_number as usize - 1)
+                        .map(|agent_log| agent_log.balance_cents)
+                        .unwrap_or(agent.balance_cents)
+                });
+            let agent_expectation = AgentExpectation::calculate(
+                &game.board,
+                &agent.strategic_bets,
+                game.even_money_rule,
+                &game.payout_table,
+            );
+            let agent_expectation_totals = self
+                .agent_expectations
+                .entry(agent.name.clone())
+                .or_insert(AgentExpectation {
+                    staked_cents_per_spin: 0,
+                    expected_loss_cents_per_spin: 0.0,
+                });
+            agent_expectation_totals.staked_cents_per_spin +=
+                agent_expectation.staked_cents_per_spin;
+            agent_expectation_totals.expected_loss_cents_per_spin +=
+                agent_expectation.expected_loss_cents_per_spin;
+            for bet in agent.strategic_bets.iter() {
+                let bet_expectation = BetExpectation::calculate(
+                    &game.board,
+                    &bet.bet_value,
+                    game.even_money_rule,
+                    &game.payout_table,
+                );
+                self.bet_totals
+                    .entry(agent.name.clone())
+                    .or_default()
+                    .entry(bet.into())
+                    .or_default()
+                    .add_bet(bet, &bet_expectation);
+            }
+        }
+    }
+
+    fn to_stats(&self) -> Stats {
+        let number_of_games = self.number_of_games;
+        return Stats {
+            average_agent_balances: self
+                .agent_balances_cents
+                .iter()
+                .map(|(agent_name, balance)| (agent_name.clone(), balance / number_of_games))
+                .collect(),
+            agent_balance_distributions: self
+                .final_agent_balances_cents
+                .iter()
+                .filter_map(|(agent_name, balances)| {
+                    Some((agent_name.clone(), balances.to_distribution()?))
+                })
+                .collect(),
+            average_bet_win_percentage: self.map_bet_totals(|bet_totals| {
+                bet_totals.win_percentage_sum / number_of_games as f64
+            }),
+            average_bet_income: self
+                .map_bet_totals(|bet_totals| bet_totals.income_cents / number_of_games),
+            bet_income_distributions: self
+                .bet_totals
+                .iter()
+                .map(|(agent_name, bet_totals)| {
+                    let distributions = bet_totals
+                        .iter()
+                        .filter_map(|(bet_hash, bet_totals)| {
+                            let distribution = bet_totals.game_incomes_cents.to_distribution()?;
+                            Some((bet_hash.clone(), distribution))
+                        })
+                        .collect();
+                    (agent_name.clone(), distributions)
+                })
+                .collect(),
+            longest_loss_streak_pet_bet: self
+                .map_bet_totals(|bet_totals| bet_totals.longest_loss_streak),
+            bet_house_edge: self.map_bet_totals(|bet_totals| bet_totals.house_edge()),
+            theoretical_bet_house_edge: self
+                .map_bet_totals(|bet_totals| bet_totals.theoretical_house_edge()),
+            bet_house_edge_z_scores: self
+                .map_bet_totals(|bet_totals| bet_totals.house_edge_z_score()),
+            trigger_fires: self.map_triggered_bet_totals(|bet_totals| {
+                bet_totals.trigger_fires as f64 / number_of_games as f64
+            }),
+            triggered_win_rates: self
+                .map_triggered_bet_totals(|bet_totals| bet_totals.triggered_win_rate()),
+            exit_reason_shares: self
+                .exit_reason_counts
+                .iter()
+                .map(|(agent_name, exit_reason_counts)| {
+                    let shares = exit_reason_counts
+                        .iter()
+                        .map(|(exit_reason, count)| {
+                            (*exit_reason, *count as f64 / number_of_games as f64)
+                        })
+                        .collect();
+                    (agent_name.clone(), shares)
+                })
+                .collect(),
+            table_limit_hits: self.map_bet_totals(|bet_totals| bet_totals.table_limit_hits),
+            ruin_stats: self
+                .ruin_totals
+                .iter()
+                .map(|(agent_name, ruin_totals)| (agent_name.clone(), ruin_totals.to_ruin_stats()))
+                .collect(),
+            balance_trajectories: self
+                .trajectory_totals
+                .iter()
+                .map(|(agent_name, trajectory_totals)| {
+                    (
+                        agent_name.clone(),
+                        trajectory_totals.to_balance_trajectory(),
+                    )
+                })
+                .collect(),
+            agent_expectations: self
+                .agent_expectations
+                .iter()
+                .map(|(agent_name, totals)| {
+                    let agent_expectation = AgentExpectation {
+                        staked_cents_per_spin: totals.staked_cents_per_spin / number_of_games,
+                        expected_loss_cents_per_spin: totals.expected_loss_cents_per_spin
+                            / number_of_games as f64,
+                    };
+                    (agent_name.clone(), agent_expectation)
+                })
+                .collect(),
+            agent_comparisons: Vec::new(),
+            roulette_type_stats: HashMap::new(),
+            even_money_rule_stats: HashMap::new(),
+            naming_scheme: NamingScheme::default(),
+        };
+    }
+
+    /// Same as `map_bet_totals`, limited to the bets placed by a trigger
+    fn map_triggered_bet_totals<T, F>(&self, f: F) -> HashMap<String, HashMap<BetHash, T>>
+    where
+        F: Fn(&BetTotals) -> T,
+    {
+        return self
+            .bet_totals
+            .iter()
+            .map(|(agent_name, bet_totals)| {
+                let values = bet_totals
+                    .iter()
+                    .filter(|(bet_hash, _)| bet_hash.has_trigger())
+                    .map(|(bet_hash, bet_totals)| (bet_hash.clone(), f(bet_totals)))
+                    .collect();
+                (agent_name.clone(), values)
+            })
+            .collect();
+    }
+
+    fn map_bet_totals<T, F>(&self, f: F) -> HashMap<String, HashMap<BetHash, T>>
+    where
+        F: Fn(&BetTotals) -> T,
+    {
+        return self
+            .bet_totals
+            .iter()
+            .map(|(agent_name, bet_totals)| {
+                let values = bet_totals
+                    .iter()
+                    .map(|(bet_hash, bet_totals)| (bet_hash.clone(), f(bet_totals)))
+                    .collect();
+                (agent_name.clone(), values)
+            })
+            .collect();
+    }
+}
+
+/// Totals per key of a breakdown, e.g. per roulette type. Nothing is kept
+/// twice while all games share one key, `totals` covers that key on its own.
+#[derive(Debug, PartialEq, Clone)]
+struct Breakdown<K: Eq + Hash> {
+    first_key: Option<K>,
+    key_totals: HashMap<K, GameTotals>,
+}
+
+impl<K> Breakdown<K>
+where
+    K: Eq + Hash + Copy,
+{
+    /// `totals` are those of the games before this one, all of which had the first key
+    fn add_game(
+        &mut self,
+        key: K,
+        totals: &GameTotals,
+        game: &RouletteGame,
+        trajectory_points: usize,
+    ) {
+        match self.first_key {
+            None => self.first_key = Some(key),
+            Some(first_key) if first_key == key && self.key_totals.is_empty() => {}
+            Some(first_key) => {
+                if self.key_totals.is_empty() {
+                    self.key_totals.insert(first_key, totals.clone());
+                }
+                self.key_totals
+                    .entry(key)
+                    .or_default()
+                    .add_game(game, trajectory_points);
+            }
+        }
+    }
+
+    /// Stats per key, only when the games don't all share the same one
+    fn to_stats(&self) -> HashMap<K, Stats> {
+        return self
+            .key_totals
+            .iter()
+            .map(|(key, key_totals)| (*key, key_totals.to_stats()))
+            .collect();
+    }
+}
+
+impl<K: Eq + Hash> Default for Breakdown<K> {
+    fn default() -> Self {
+        return Breakdown {
+            first_key: None,
+            key_totals: HashMap::new(),
+        };
+    }
+}
+
+/// Folds finished games into `Stats` one at a time, so a run doesn't have to
+/// keep its games around. Its memory depends on the agents, bets and rounds
+/// but not on the number of games. Games are expected in the same order on
+/// every run for the float sums to come out the same.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StatsAccumulator {
+    /// Rounds sampled for the balance trajectories
+    trajectory_points: usize,
+    totals: GameTotals,
+    roulette_type_totals: Breakdown<RouletteType>,
+    even_money_rule_totals: Breakdown<EvenMoneyRule>,
+    /// Only when the agents played the same spins, which makes their final
+    /// balances comparable game by game
+    comparison_totals: Option<ComparisonTotals>,
+}
+
+impl StatsAccumulator {
+    pub fn new(trajectory_points: usize, compare_agents: bool) -> Self {
+        return StatsAccumulator {
+            trajectory_points,
+            totals: GameTotals::default(),
+            roulette_type_totals: Breakdown::default(),
+            even_money_rule_totals: Breakdown::default(),
+            comparison_totals: compare_agents.then(ComparisonTotals::default),
+        };
+    }
+
+    pub fn add_game(&mut self, game: &RouletteGame) {
+        self.roulette_type_totals.add_game(
+            game.roulette_type,
+            &self.totals,
+            game,
+            self.trajectory_points,
+        );
+        self.even_money_rule_totals.add_game(
+            game.even_money_rule,
+            &self.totals,
+            game,
+            self.trajectory_points,
+        );
+        self.totals.add_game(game, self.trajectory_points);
+        if let Some(comparison_totals) = self.comparison_totals.as_mut() {
+            comparison_totals.add_game(game);
+        }
+    }
+
+    pub fn to_stats(&self) -> Stats {
+        let mut stats = self.totals.to_stats();
+        if let Some(comparison_totals) = &self.comparison_totals {
+            stats.agent_comparisons = comparison_totals.to_agent_comparisons();
+        }
+        stats.roulette_type_stats = self.roulette_type_totals.to_stats();
+        stats.even_money_rule_stats = self.even_money_rule_totals.to_stats();
+        return stats;
+    }
+}
+
+impl Default for StatsAccumulator {
+    fn default() -> Self {
+        return StatsAccumulator::new(DEFAULT_TRAJECTORY_POINTS, false);
+    }
+}
@@ -0,0 +1,263 @@
+use itertools::iproduct;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+use super::{game_configs::GameConfig, sweep_point::SweepPoint};
+
+/// Parameter points a single sweep may run, each point being a full run
+pub const MAX_SWEEP_POINTS: usize = 1000;
+/// Games a single sweep may play over all of its points
+pub const MAX_SWEEP_GAMES: i64 = 10_000_000;
+
+/// Values a swept parameter takes, either listed or stepped through
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SweepValues {
+    List(Vec<i64>),
+    /// From `from` up to and including `to`, `step` apart (1 when left out)
+    Range {
+        from: i64,
+        to: i64,
+        step: Option<i64>,
+    },
+}
+
+impl SweepValues {
+    pub fn get_values(&self) -> Vec<i64> {
+        return match self {
+            SweepValues::List(values) => values.clone(),
+            SweepValues::Range { from, to, step } => {
+                let step = step.unwrap_or(1).max(1) as usize;
+                (*from..=*to).step_by(step).collect()
+            }
+        };
+    }
+
+    /// Number of values, counted without listing them so huge ranges are cheap to reject
+    pub fn get_number_of_values(&self) -> usize {
+        return match self {
+            SweepValues::List(values) => values.len(),
+            SweepValues::Range { from, to, step } if from <= to => {
+                let step = step.unwrap_or(1).max(1) as i128;
+                let count = (*to as i128 - *from as i128) / step + 1;
+                usize::try_from(count).unwrap_or(usize::MAX)
+            }
+            SweepValues::Range { .. } => 0,
+        };
+    }
+
+    fn find_problem(&self, min_value: i64) -> Option<String> {
+        if let SweepValues::Range { from, to, step } = self {
+            if from > to {
+                return Some(format!("range from {} to {} is empty", from, to));
+            }
+            if step.is_some_and(|step| step < 1) {
+                return Some("range step has to be at least 1".to_string());
+            }
+        }
+        let values = self.get_values();
+        if values.is_empty() {
+            return Some("has no values".to_string());
+        }
+        return values
+            .iter()
+            .find(|value| **value < min_value)
+            .map(|value| format!("{} is below {}", value, min_value));
+    }
+}
+
+/// Parameters to vary over a set of runs, left out parameters keep the values
+/// of the game config and agents
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct SweepConfig {
+    pub balance_cents: Option<SweepValues>,
+    /// Opening stake of every bet
+    pub amount_cents: Option<SweepValues>,
+    pub progression_factor: Option<SweepValues>,
+    pub number_of_rounds: Option<SweepValues>,
+}
+
+impl SweepConfig {
+    /// Checks the swept values and that the sweep of `game_config` stays within
+    /// `MAX_SWEEP_POINTS` and `MAX_SWEEP_GAMES`
+    pub fn validate(&self, game_config: &GameConfig) -> Result<(), Error> {
+        let number_of_points = self.get_number_of_points();
+        if number_of_points > MAX_SWEEP_POINTS {
+            return Err(Error::GenericError {
+                message: format!(
+                    "Sweep has {} points, at most {} are allowed",
+                    number_of_points, MAX_SWEEP_POINTS
+                ),
+                nested_error: None,
+            });
+        }
+        let number_of_games =
+            number_of_points as i64 * game_config.get_total_number_of_games().max(0) as i64;
+        if number_of_games > MAX_SWEEP_GAMES {
+            return Err(Error::GenericError {
+                message: format!(
+                    "Sweep plays {} games over {} points, at most {} are allowed",
+                    number_of_games, number_of_points, MAX_SWEEP_GAMES
+                ),
+                nested_error: None,
+            });
+        }
+        let parameters = [
+            ("balance_cents", &self.balance_cents, 0),
+            ("amount_cents", &self.amount_cents, 1),
+            ("progression_factor", &self.progression_factor, 0),
+            ("number_of_rounds", &self.number_of_rounds, 1),
+        ];
+        for (name, sweep_values, min_value) in parameters {
+            if let Some(problem) = sweep_values
+                .as_ref()
+                .and_then(|sweep_values| sweep_values.find_problem(min_value))
+            {
+                return Err(Error::GenericError {
+                    message: format!("Sweep of {} {}", name, problem),
+                    nested_error: None,
+                });
+            }
+        }
+        if let Some(rounds) = Self::values_of(&self.number_of_rounds)
+            .into_iter()
+            .flatten()
+            .find(|rounds| *rounds > i32::MAX as i64)
+        {
+            return Err(Error::GenericError {
+                message: format!("Sweep of number_of_rounds {} is too many rounds", rounds),
+                nested_error: None,
+            });
+        }
+        return Ok(());
+    }
+
+    pub fn get_number_of_points(&self) -> usize {
+        return [
+            &self.balance_cents,
+            &self.amount_cents,
+            &self.progression_factor,
+            &self.number_of_rounds,
+        ]
+        .iter()
+        .map(|sweep_values| {
+            sweep_values
+                .as_ref()
+                .map_or(1, |sweep_values| sweep_values.get_number_of_values())
+        })
+        .fold(1, usize::saturating_mul);
+    }
+
+    /// Cartesian product of the swept values, in the order the parameters are declared
+    pub fn get_points(&self) -> Vec<SweepPoint> {
+        return iproduct!(
+            Self::values_of(&self.balance_cents),
+            Self::values_of(&self.amount_cents),
+            Self::values_of(&self.progression_factor),
+            Self::values_of(&self.number_of_rounds)
+        )
+        .map(
+            |(balance_cents, amount_cents, progression_factor, number_of_rounds)| SweepPoint {
+                balance_cents,
+                amount_cents,
+                progression_factor,
+                number_of_rounds: number_of_rounds.map(|rounds| rounds as i32),
+            },
+        )
+        .collect();
+    }
+
+    /// The swept values, or a single `None` for a parameter that isn't swept
+    fn values_of(sweep_values: &Option<SweepValues>) -> Vec<Option<i64>> {
+        return match sweep_values {
+            Some(sweep_values) => sweep_values.get_values().into_iter().map(Some).collect(),
+            None => vec![None],
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_get_values() {
+        assert_eq!(SweepValues::List(vec![5, 1]).get_values(), vec![5, 1]);
+        let sweep_values: SweepValues =
+            serde_json::from_value(json!({"from": 100, "to": 500, "step": 200})).unwrap();
+        assert_eq!(sweep_values.get_values(), vec![100, 300, 500]);
+        let sweep_values: SweepValues =
+            serde_json::from_value(json!({"from": 1, "to": 3})).unwrap();
+        assert_eq!(sweep_values.get_values(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_get_points() {
+        let sweep_config: SweepConfig = serde_json::from_value(json!({
+            "balance_cents": [10000, 20000],
+            "progression_factor": {"from": 1, "to": 3}
+        }))
+        .unwrap();
+        let points = sweep_config.get_points();
+        assert_eq!(sweep_config.get_number_of_points(), 6);
+        assert_eq!(points.len(), 6);
+        assert_eq!(
+            points[4],
+            SweepPoint {
+                balance_cents: Some(20000),
+                amount_cents: None,
+                progression_factor: Some(2),
+                number_of_rounds: None,
+            }
+        );
+        assert_eq!(
+            SweepConfig::default().get_points(),
+            vec![SweepPoint::default()]
+        );
+    }
+
+    fn create_game_config(number_of_games: i32) -> GameConfig {
+        return serde_json::from_value(json!({
+            "number_of_games": number_of_games,
+            "number_of_rounds": 100,
+            "allow_negative_balance": false
+        }))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        let game_config = create_game_config(100);
+        let valid: SweepConfig =
+            serde_json::from_value(json!({"amount_cents": [100, 500]})).unwrap();
+        assert!(valid.validate(&game_config).is_ok());
+        for sweep_config in [
+            json!({"amount_cents": [0, 500]}),
+            json!({"balance_cents": []}),
+            json!({"number_of_rounds": {"from": 10, "to": 1}}),
+            json!({"progression_factor": {"from": 1, "to": 3, "step": 0}}),
+            json!({"number_of_rounds": [3000000000i64]}),
+            json!({"balance_cents": {"from": 1, "to": 100}, "amount_cents": {"from": 1, "to": 11}}),
+            json!({"balance_cents": {"from": 0, "to": i64::MAX}}),
+        ] {
+            let sweep_config: SweepConfig = serde_json::from_value(sweep_config).unwrap();
+            assert!(sweep_config.validate(&game_config).is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_number_of_games() {
+        let sweep_config: SweepConfig =
+            serde_json::from_value(json!({"amount_cents": {"from": 100, "to": 1000, "step": 100}}))
+                .unwrap();
+        assert!(sweep_config
+            .validate(&create_game_config(MAX_SWEEP_GAMES as i32 / 10))
+            .is_ok());
+        assert!(sweep_config
+            .validate(&create_game_config(MAX_SWEEP_GAMES as i32 / 10 + 1))
+            .is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::agent::Agent;
+use crate::strategy::strategy::Strategy;
+
+use super::game_configs::GameConfig;
+
+/// One combination of swept parameters, `None` where the parameter isn't swept
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct SweepPoint {
+    pub balance_cents: Option<i64>,
+    pub amount_cents: Option<i64>,
+    pub progression_factor: Option<i64>,
+    pub number_of_rounds: Option<i32>,
+}
+
+impl SweepPoint {
+    /// Copies of the game config and agents with the parameters of this point,
+    /// applied to every agent and every bet alike. Amounts of bets spread over
+    /// several chips are rounded down to whole chips.
+    pub fn apply(&self, game_config: &GameConfig, agents: &[Agent]) -> (GameConfig, Vec<Agent>) {
+        let mut game_config = game_config.clone();
+        if let Some(number_of_rounds) = self.number_of_rounds {
+            game_config.number_of_rounds = number_of_rounds;
+        }
+        let agents = agents
+            .iter()
+            .cloned()
+            .map(|mut agent| {
+                if let Some(balance_cents) = self.balance_cents {
+                    agent.balance_cents = balance_cents;
+                    agent.starting_balance_cents = balance_cents;
+                    agent.peak_balance_cents = balance_cents;
+                }
+                for bet in agent.strategic_bets.iter_mut() {
+                    if let Some(amount_cents) = self.amount_cents {
+                        let chip_count = bet.bet_value.get_chip_count();
+                        let amount_cents =
+                            (amount_cents - amount_cents % chip_count).max(chip_count);
+                        bet.initial_amount_cents = amount_cents;
+                        bet.amount_cents = bet.strategy.opening_amount_cents(amount_cents);
+                    }
+                    if let Some(progression_factor) = self.progression_factor {
+                        bet.progression_factor = progression_factor;
+                    }
+                }
+                agent
+            })
+            .collect();
+        return (game_config, agents);
+    }
+}
+
+impl fmt::Display for SweepPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parameters = Vec::new();
+        if let Some(balance_cents) = self.balance_cents {
+            parameters.push(format!("balance_cents={}", balance_cents));
+        }
+        if let Some(amount_cents) = self.amount_cents {
+            parameters.push(format!("amount_cents={}", amount_cents));
+        }
+        if let Some(progression_factor) = self.progression_factor {
+            parameters.push(format!("progression_factor={}", progression_factor));
+        }
+        if let Some(number_of_rounds) = self.number_of_rounds {
+            parameters.push(format!("number_of_rounds={}", number_of_rounds));
+        }
+        if parameters.is_empty() {
+            return write!(f, "unchanged parameters");
+        }
+        write!(f, "{}", parameters.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::json_reader::JsonReader;
+
+    #[test]
+    fn test_apply() {
+        let game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let point = SweepPoint {
+            balance_cents: Some(5000),
+            amount_cents: Some(250),
+            progression_factor: None,
+            number_of_rounds: Some(7),
+        };
+        let (swept_game_config, swept_agents) = point.apply(&game_config, &agents);
+        assert_eq!(swept_game_config.number_of_rounds, 7);
+        assert_eq!(
+            swept_game_config.number_of_games,
+            game_config.number_of_games
+        );
+        assert_eq!(swept_agents.len(), agents.len());
+        for (swept_agent, agent) in swept_agents.iter().zip(agents.iter()) {
+            assert_eq!(swept_agent.balance_cents, 5000);
+            for (swept_bet, bet) in swept_agent
+                .strategic_bets
+                .iter()
+                .zip(agent.strategic_bets.iter())
+            {
+                assert_eq!(swept_bet.initial_amount_cents, 250);
+                assert_eq!(swept_bet.progression_factor, bet.progression_factor);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let point = SweepPoint {
+            amount_cents: Some(100),
+            progression_factor: Some(3),
+            ..SweepPoint::default()
+        };
+        assert_eq!(point.to_string(), "amount_cents=100, progression_factor=3");
+        assert_eq!(SweepPoint::default().to_string(), "unchanged parameters");
+    }
+}
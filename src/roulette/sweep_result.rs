@@ -0,0 +1,162 @@
+use core::fmt;
+
+use itertools::Itertools;
+use prettytable::{Cell, Row, Table};
+use serde::{Deserialize, Serialize};
+
+use super::{run_result::RunResult, sweep_point::SweepPoint};
+
+const CSV_HEADER: &str = "balance_cents,amount_cents,progression_factor,number_of_rounds,\
+agent_name,mean_balance_cents,median_balance_cents,ruin_rate,failed_games";
+
+/// Key stats of one agent at one point of a sweep
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SweepRow {
+    #[serde(flatten)]
+    pub point: SweepPoint,
+    pub agent_name: String,
+    pub mean_balance_cents: i64,
+    pub median_balance_cents: f64,
+    /// Share of games the agent went broke
+    pub ruin_rate: f64,
+    /// Games of the point's run that failed, for all of its agents
+    pub failed_games: i32,
+}
+
+impl SweepRow {
+    /// One row per agent of the run, by agent name
+    pub fn from_run(point: &SweepPoint, run_result: &RunResult) -> Vec<Self> {
+        let stats = &run_result.stats;
+        return stats
+            .average_agent_balances
+            .iter()
+            .sorted_by_key(|(agent_name, _)| agent_name.to_string())
+            .map(|(agent_name, mean_balance_cents)| SweepRow {
+                point: point.clone(),
+                agent_name: agent_name.clone(),
+                mean_balance_cents: *mean_balance_cents,
+                median_balance_cents: stats
+                    .agent_balance_distributions
+                    .get(agent_name)
+                    .map(|distribution| distribution.median)
+                    .unwrap_or(*mean_balance_cents as f64),
+                ruin_rate: stats
+                    .ruin_stats
+                    .get(agent_name)
+                    .map(|ruin_stats| ruin_stats.ruin_share)
+                    .unwrap_or(0.0),
+                failed_games: run_result.failed_games,
+            })
+            .collect();
+    }
+
+    fn to_csv_line(&self) -> String {
+        let optional =
+            |value: Option<i64>| value.map(|value| value.to_string()).unwrap_or_default();
+        return [
+            optional(self.point.balance_cents),
+            optional(self.point.amount_cents),
+            optional(self.point.progression_factor),
+            optional(self.point.number_of_rounds.map(|rounds| rounds as i64)),
+            Self::escape_csv(&self.agent_name),
+            self.mean_balance_cents.to_string(),
+            self.median_balance_cents.to_string(),
+            self.ruin_rate.to_string(),
+            self.failed_games.to_string(),
+        ]
+        .join(",");
+    }
+
+    fn escape_csv(field: &str) -> String {
+        if field.contains([',', '"', '\n', '\r']) {
+            return format!("\"{}\"", field.replace('"', "\"\""));
+        }
+        return field.to_string();
+    }
+}
+
+/// Outcome of a sweep, in the order its points were run
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct SweepResult {
+    pub rows: Vec<SweepRow>,
+}
+
+impl SweepResult {
+    /// Parameters that weren't swept are left empty
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for row in self.rows.iter() {
+            csv.push_str(&row.to_csv_line());
+            csv.push('\n');
+        }
+        return csv;
+    }
+}
+
+impl fmt::Display for SweepResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            Cell::new("PARAMETERS"),
+            Cell::new("AGENT"),
+            Cell::new("MEAN BALANCE"),
+            Cell::new("MEDIAN BALANCE"),
+            Cell::new("RUIN RATE"),
+        ]));
+        self.rows.iter().for_each(|row| {
+            table.add_row(Row::new(vec![
+                Cell::new(&row.point.to_string()),
+                Cell::new(&row.agent_name),
+                Cell::new(&format!("${:.2}", row.mean_balance_cents as f64 / 100.0)),
+                Cell::new(&format!("${:.2}", row.median_balance_cents / 100.0)),
+                Cell::new(&format!("{:.2}%", row.ruin_rate * 100.0)),
+            ]));
+        });
+        write!(f, "\nParameter Sweep:\n{}", table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_row(agent_name: &str) -> SweepRow {
+        return SweepRow {
+            point: SweepPoint {
+                amount_cents: Some(500),
+                number_of_rounds: Some(100),
+                ..SweepPoint::default()
+            },
+            agent_name: agent_name.to_string(),
+            mean_balance_cents: 9500,
+            median_balance_cents: 9250.5,
+            ruin_rate: 0.25,
+            failed_games: 0,
+        };
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let sweep_result = SweepResult {
+            rows: vec![create_row("Agent 1"), create_row("Martingale, \"big\"")],
+        };
+        let csv = sweep_result.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines[1], ",500,,100,Agent 1,9500,9250.5,0.25,0");
+        assert_eq!(
+            lines[2],
+            ",500,,100,\"Martingale, \"\"big\"\"\",9500,9250.5,0.25,0"
+        );
+    }
+
+    #[test]
+    fn test_serialize_flattens_point() {
+        let row = serde_json::to_value(create_row("Agent 1")).unwrap();
+        assert_eq!(row["amount_cents"], 500);
+        assert!(row["balance_cents"].is_null());
+        assert_eq!(row["ruin_rate"], 0.25);
+    }
+}
@@ -0,0 +1,104 @@
+use tracing::info;
+
+use crate::agent::agent::Agent;
+use crate::error::Error;
+
+use super::{
+    game_configs::GameConfig,
+    game_runner,
+    log_retention::LogRetention,
+    run_controls::RunControls,
+    sweep_config::SweepConfig,
+    sweep_result::{SweepResult, SweepRow},
+};
+
+/// Runs the agents once for every point of the sweep, one point after the other.
+/// A point whose run fails fails the whole sweep.
+pub async fn run_sweep(
+    game_config: GameConfig,
+    agents: Vec<Agent>,
+    sweep_config: &SweepConfig,
+) -> Result<SweepResult, Error> {
+    return run_sweep_with_controls(game_config, agents, sweep_config, RunControls::default())
+        .await;
+}
+
+/// Same as `run_sweep`, but can be cancelled through `controls`, which every
+/// point's run is handed as well. A cancelled sweep fails as a whole.
+pub async fn run_sweep_with_controls(
+    mut game_config: GameConfig,
+    agents: Vec<Agent>,
+    sweep_config: &SweepConfig,
+    controls: RunControls,
+) -> Result<SweepResult, Error> {
+    sweep_config.validate(&game_config)?;
+    // only the stats of each point are looked at
+    game_config.log_retention = Some(LogRetention::None);
+    let points = sweep_config.get_points();
+    let mut sweep_result = SweepResult::default();
+    for (index, point) in points.iter().enumerate() {
+        if controls.is_cancelled() {
+            return Err(Error::GenericError {
+                message: "Sweep was cancelled".to_string(),
+                nested_error: None,
+            });
+        }
+        info!("Sweep point {} of {}: {}", index + 1, points.len(), point);
+        let (point_game_config, point_agents) = point.apply(&game_config, &agents);
+        let run_result =
+            game_runner::run_with_controls(point_game_config, point_agents, controls.clone())
+                .await?;
+        sweep_result
+            .rows
+            .extend(SweepRow::from_run(point, &run_result));
+    }
+    return Ok(sweep_result);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::json::json_reader::JsonReader;
+    use crate::roulette::sweep_config::SweepValues;
+
+    #[tokio::test]
+    async fn test_run_sweep() {
+        let mut game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        game_config.number_of_games = 5;
+        game_config.seed = Some(3);
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let sweep_config = SweepConfig {
+            amount_cents: Some(SweepValues::List(vec![100, 1000])),
+            number_of_rounds: Some(SweepValues::Range {
+                from: 10,
+                to: 30,
+                step: Some(10),
+            }),
+            ..SweepConfig::default()
+        };
+
+        let sweep_result = run_sweep(game_config, agents.clone(), &sweep_config)
+            .await
+            .unwrap();
+        assert_eq!(sweep_result.rows.len(), 6 * agents.len());
+        let first_row = &sweep_result.rows[0];
+        assert_eq!(first_row.point.amount_cents, Some(100));
+        assert_eq!(first_row.point.number_of_rounds, Some(10));
+        assert_eq!(first_row.point.balance_cents, None);
+        assert!(sweep_result
+            .rows
+            .iter()
+            .all(|row| row.failed_games == 0 && (0.0..=1.0).contains(&row.ruin_rate)));
+    }
+
+    #[tokio::test]
+    async fn test_run_sweep_invalid() {
+        let game_config = JsonReader::read_game_json("./res/tst/game.json").unwrap();
+        let agents = JsonReader::read_agents_json("./res/tst/agents.json").unwrap();
+        let sweep_config = SweepConfig {
+            amount_cents: Some(SweepValues::List(vec![])),
+            ..SweepConfig::default()
+        };
+        assert!(run_sweep(game_config, agents, &sweep_config).await.is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bet::{bet_value::BetValue, naming_scheme::NamingScheme},
+    error::Error,
+};
+
+use super::limit_policy::LimitPolicy;
+
+/// Names accepted as keys of `bet_type_maximums_cents`, same as in agents.json
+/// where the casino names are accepted too
+const BET_TYPE_NAMES: [&str; 11] = [
+    "AdjacentNumbers",
+    "CallBet",
+    "Color",
+    "Column",
+    "DoubleColumn",
+    "Dozen",
+    "EvenOdd",
+    "Half",
+    "Neighbours",
+    "Number",
+    "Row",
+];
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableLimits {
+    /// Smallest stake accepted on any single bet
+    pub table_minimum_cents: Option<i64>,
+    /// Largest stake accepted on a single inside bet (`Number`, `AdjacentNumbers`),
+    /// per chip of a `CallBet` or `Neighbours`
+    pub inside_maximum_cents: Option<i64>,
+    /// Largest stake accepted per bet type, keyed like the bet values in agents.json
+    pub bet_type_maximums_cents: BTreeMap<String, i64>,
+    pub limit_policy: LimitPolicy,
+}
+
+impl TableLimits {
+    pub fn validate(&self) -> Result<(), Error> {
+        for (bet_type, maximum_cents) in self.bet_type_maximums_cents.iter() {
+            if !BET_TYPE_NAMES.contains(&NamingScheme::to_classic_name(bet_type)) {
+                return Err(Error::GenericError {
+                    message: format!("Unknown bet type in table limits: {}", bet_type),
+                    nested_error: None,
+                });
+            }
+            self.validate_maximum(*maximum_cents)?;
+        }
+        if let Some(inside_maximum_cents) = self.inside_maximum_cents {
+            self.validate_maximum(inside_maximum_cents)?;
+        }
+        return Ok(());
+    }
+
+    fn validate_maximum(&self, maximum_cents: i64) -> Result<(), Error> {
+        let minimum_cents = self.table_minimum_cents.unwrap_or(1);
+        if maximum_cents < minimum_cents {
+            return Err(Error::GenericError {
+                message: format!(
+                    "Table maximum of {} cents is below the table minimum of {} cents",
+                    maximum_cents, minimum_cents
+                ),
+                nested_error: None,
+            });
+        }
+        return Ok(());
+    }
+
+    /// Lowest of the maximums that apply to `bet_value`, if any
+    pub fn maximum_for(&self, bet_value: &BetValue) -> Option<i64> {
+        let inside_maximum_cents = if bet_value.is_inside() {
+            self.inside_maximum_cents
+                .map(|maximum_cents| maximum_cents * bet_value.get_chip_count())
+        } else {
+            None
+        };
+        let bet_type_maximum_cents = self
+            .bet_type_maximums_cents
+            .iter()
+            .filter(|(bet_type, _)| {
+                NamingScheme::to_classic_name(bet_type) == bet_value.get_variant_name()
+            })
+            .map(|(_, maximum_cents)| *maximum_cents)
+            .min();
+        return match (inside_maximum_cents, bet_type_maximum_cents) {
+            (Some(inside), Some(bet_type)) => Some(inside.min(bet_type)),
+            (inside, bet_type) => inside.or(bet_type),
+        };
+    }
+
+    pub fn is_below_minimum(&self, amount_cents: i64) -> bool {
+        return self
+            .table_minimum_cents
+            .is_some_and(|minimum_cents| amount_cents < minimum_cents);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        adjacent_numbers::AdjacentNumbers, call_bet::CallBet, color::Color, dozen::Dozen, row::Row,
+    };
+
+    fn create_table_limits() -> TableLimits {
+        return TableLimits {
+            table_minimum_cents: Some(500),
+            inside_maximum_cents: Some(10000),
+            bet_type_maximums_cents: BTreeMap::from([
+                ("Color".to_string(), 50000),
+                ("Number".to_string(), 5000),
+            ]),
+            limit_policy: LimitPolicy::Clamp,
+        };
+    }
+
+    #[test]
+    fn test_maximum_for() {
+        let table_limits = create_table_limits();
+        assert_eq!(table_limits.maximum_for(&BetValue::Number(17)), Some(5000));
+        assert_eq!(
+            table_limits.maximum_for(&BetValue::Color(Color::Red)),
+            Some(50000)
+        );
+        assert_eq!(
+            table_limits.maximum_for(&BetValue::AdjacentNumbers(AdjacentNumbers {
+                numbers: vec![1, 2]
+            })),
+            Some(10000)
+        );
+        assert_eq!(
+            table_limits.maximum_for(&BetValue::CallBet(CallBet::Orphelins)),
+            Some(50000)
+        );
+        assert_eq!(
+            table_limits.maximum_for(&BetValue::Neighbours {
+                center: 17,
+                radius: 1
+            }),
+            Some(30000)
+        );
+        assert_eq!(table_limits.maximum_for(&BetValue::Dozen(Dozen::One)), None);
+        let mut table_limits = create_table_limits();
+        table_limits
+            .bet_type_maximums_cents
+            .insert("TableColumn".to_string(), 20000);
+        assert!(table_limits.validate().is_ok());
+        assert_eq!(
+            table_limits.maximum_for(&BetValue::Row(Row::Two)),
+            Some(20000)
+        );
+    }
+
+    #[test]
+    fn test_is_below_minimum() {
+        let table_limits = create_table_limits();
+        assert!(table_limits.is_below_minimum(499));
+        assert!(!table_limits.is_below_minimum(500));
+        assert!(!TableLimits::default().is_below_minimum(1));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(create_table_limits().validate().is_ok());
+        let mut table_limits = create_table_limits();
+        table_limits
+            .bet_type_maximums_cents
+            .insert("Straight".to_string(), 1000);
+        assert!(table_limits.validate().is_err());
+        let mut table_limits = create_table_limits();
+        table_limits.inside_maximum_cents = Some(100);
+        assert!(table_limits.validate().is_err());
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+/// Adds one unit to the stake after a loss and removes one after a win,
+/// never going below a single unit.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct DAlembert {}
+
+impl Strategy for DAlembert {
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        if context.is_loss() {
+            return context.amount_cents + context.initial_amount_cents;
+        }
+        return (context.amount_cents - context.initial_amount_cents)
+            .max(context.initial_amount_cents);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_next_amount_cents() {
+        assert_eq!(
+            play_outcomes(&mut DAlembert {}, &[false, false, true, true, true]),
+            vec![200, 300, 200, 100, 100]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+/// Moves one step up the Fibonacci sequence after a loss and two steps back
+/// after a win, staking that many units.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct Fibonacci {
+    pub step: usize,
+}
+
+impl Fibonacci {
+    fn units(step: usize) -> i64 {
+        let (mut previous, mut current): (i64, i64) = (0, 1);
+        for _ in 0..step {
+            (previous, current) = (current, previous.saturating_add(current));
+        }
+        return current;
+    }
+}
+
+impl Strategy for Fibonacci {
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        if context.is_loss() {
+            self.step += 1;
+        } else {
+            self.step = self.step.saturating_sub(2);
+        }
+        return context.initial_amount_cents * Self::units(self.step);
+    }
+
+    fn reset(&mut self) {
+        self.step = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_units() {
+        let units: Vec<i64> = (0..7).map(Fibonacci::units).collect();
+        assert_eq!(units, vec![1, 1, 2, 3, 5, 8, 13]);
+    }
+
+    #[test]
+    fn test_next_amount_cents() {
+        assert_eq!(
+            play_outcomes(
+                &mut Fibonacci::default(),
+                &[false, false, false, false, true, true]
+            ),
+            vec![100, 200, 300, 500, 200, 100]
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+/// Doubles the stake and adds one more unit after a loss, goes back to the
+/// initial stake after a win.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct GrandMartingale {}
+
+impl Strategy for GrandMartingale {
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        if context.is_loss() {
+            return context.amount_cents * 2 + context.initial_amount_cents;
+        }
+        return context.initial_amount_cents;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_next_amount_cents() {
+        assert_eq!(
+            play_outcomes(&mut GrandMartingale {}, &[false, false, true]),
+            vec![300, 700, 100]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+/// Stakes the sum of the first and last numbers of a line of units. A win
+/// crosses both numbers out, a loss appends the lost stake to the line. The
+/// line starts over once every number has been crossed out.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct Labouchere {
+    pub sequence: Vec<i64>,
+    pub remaining: Vec<i64>,
+}
+
+impl Default for Labouchere {
+    fn default() -> Self {
+        return Labouchere::new(vec![1, 2, 3, 4]);
+    }
+}
+
+impl Labouchere {
+    pub fn new(sequence: Vec<i64>) -> Self {
+        return Labouchere {
+            remaining: sequence.clone(),
+            sequence,
+        };
+    }
+
+    fn current_units(&self) -> i64 {
+        return match self.remaining.len() {
+            0 => 1,
+            1 => self.remaining[0],
+            len => self.remaining[0] + self.remaining[len - 1],
+        };
+    }
+}
+
+impl Strategy for Labouchere {
+    fn opening_amount_cents(&self, initial_amount_cents: i64) -> i64 {
+        return initial_amount_cents * self.current_units();
+    }
+
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        if context.is_loss() {
+            let lost_units = self.current_units();
+            self.remaining.push(lost_units);
+        } else {
+            let crossed_out = self.remaining.len().min(2);
+            self.remaining.remove(0);
+            if crossed_out == 2 {
+                self.remaining.pop();
+            }
+            if self.remaining.is_empty() {
+                self.remaining = self.sequence.clone();
+            }
+        }
+        return context.initial_amount_cents * self.current_units();
+    }
+
+    fn reset(&mut self) {
+        self.remaining = self.sequence.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_opening_amount_cents() {
+        assert_eq!(
+            Labouchere::new(vec![1, 2, 3]).opening_amount_cents(100),
+            400
+        );
+    }
+
+    #[test]
+    fn test_next_amount_cents() {
+        let mut labouchere = Labouchere::new(vec![1, 2, 3]);
+        assert_eq!(
+            play_outcomes(&mut labouchere, &[false, true, true, true]),
+            vec![500, 500, 400, 200]
+        );
+        assert_eq!(labouchere.remaining, vec![2]);
+        labouchere.reset();
+        assert_eq!(labouchere.remaining, vec![1, 2, 3]);
+    }
+}
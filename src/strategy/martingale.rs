@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+/// Multiplies the stake by the bet's progression factor after a loss and
+/// goes back to the initial stake after a win.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct Martingale {}
+
+impl Strategy for Martingale {
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        if context.is_loss() {
+            return context.amount_cents * context.progression_factor;
+        }
+        return context.initial_amount_cents;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_next_amount_cents() {
+        assert_eq!(
+            play_outcomes(&mut Martingale {}, &[false, false, true, false]),
+            vec![200, 400, 100, 200]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+pub mod d_alembert;
+pub mod fibonacci;
+pub mod grand_martingale;
+pub mod labouchere;
+pub mod martingale;
+pub mod one_three_two_six;
+pub mod oscars_grind;
+pub mod paroli;
+#[allow(clippy::module_inception)]
+pub mod strategy;
+pub mod strategy_type;
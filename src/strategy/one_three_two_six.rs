@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+const UNITS: [i64; 4] = [1, 3, 2, 6];
+
+/// Stakes 1, 3, 2 and then 6 units on consecutive wins, starting over after a
+/// loss or once the sixth-unit bet has been played.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct OneThreeTwoSix {
+    pub step: usize,
+}
+
+impl Strategy for OneThreeTwoSix {
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        if context.is_win() {
+            self.step = (self.step + 1) % UNITS.len();
+        } else {
+            self.step = 0;
+        }
+        return context.initial_amount_cents * UNITS[self.step];
+    }
+
+    fn reset(&mut self) {
+        self.step = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_next_amount_cents() {
+        assert_eq!(
+            play_outcomes(
+                &mut OneThreeTwoSix::default(),
+                &[true, true, true, true, true, false]
+            ),
+            vec![300, 200, 600, 100, 300, 100]
+        );
+    }
+}
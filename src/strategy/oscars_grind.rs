@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+/// Aims for one unit of profit per cycle. The stake stays the same after a
+/// loss and grows by one unit after a win, but never beyond what is needed to
+/// finish the cycle one unit up.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct OscarsGrind {
+    pub cycle_income_cents: i64,
+}
+
+impl Strategy for OscarsGrind {
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        let unit = context.initial_amount_cents;
+        self.cycle_income_cents += context.last_income_cents();
+        if self.cycle_income_cents >= unit {
+            self.cycle_income_cents = 0;
+            return unit;
+        }
+        if context.is_loss() {
+            return context.amount_cents;
+        }
+        return (context.amount_cents + unit)
+            .min(unit - self.cycle_income_cents)
+            .max(unit);
+    }
+
+    fn reset(&mut self) {
+        self.cycle_income_cents = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_next_amount_cents() {
+        let mut oscars_grind = OscarsGrind::default();
+        assert_eq!(
+            play_outcomes(&mut oscars_grind, &[false, false, true, true, true]),
+            vec![100, 100, 200, 100, 100]
+        );
+        assert_eq!(oscars_grind.cycle_income_cents, 0);
+    }
+}
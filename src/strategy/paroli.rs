@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use super::strategy::{Strategy, StrategyContext};
+
+const WINS_PER_CYCLE: u32 = 3;
+
+/// Doubles the stake after a win and goes back to the initial stake after a
+/// loss or after three wins in a row.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct Paroli {
+    pub wins: u32,
+}
+
+impl Strategy for Paroli {
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        if context.is_win() {
+            self.wins += 1;
+            if self.wins < WINS_PER_CYCLE {
+                return context.amount_cents * 2;
+            }
+        }
+        self.wins = 0;
+        return context.initial_amount_cents;
+    }
+
+    fn reset(&mut self) {
+        self.wins = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::strategy::test_utils::play_outcomes;
+
+    #[test]
+    fn test_next_amount_cents() {
+        assert_eq!(
+            play_outcomes(
+                &mut Paroli::default(),
+                &[true, true, true, true, false, true]
+            ),
+            vec![200, 400, 100, 200, 100, 200]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::bet::{bet_log::BetLog, bet_state::BetState};
+use crate::roulette::game_logs::GameLog;
+
+/// Everything a strategy may look at when sizing the next stake of a bet.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct StrategyContext<'a> {
+    pub bet_state: BetState,
+    pub bet_logs: &'a [BetLog],
+    pub amount_cents: i64,
+    pub initial_amount_cents: i64,
+    pub progression_factor: i64,
+    /// Balance of the agent once the round was settled
+    pub balance_cents: i64,
+    /// Every spin of the game so far
+    pub game_logs: &'a [GameLog],
+}
+
+impl StrategyContext<'_> {
+    pub fn is_win(&self) -> bool {
+        return self.bet_state == BetState::Won;
+    }
+
+    pub fn is_loss(&self) -> bool {
+        return self.bet_state.is_loss();
+    }
+
+    /// Net income of the round that was just settled
+    pub fn last_income_cents(&self) -> i64 {
+        return self
+            .bet_logs
+            .last()
+            .map(|bet_log| bet_log.income_cents())
+            .unwrap_or(0);
+    }
+}
+
+pub trait Strategy {
+    /// Stake of the very first round, before any result is known.
+    fn opening_amount_cents(&self, initial_amount_cents: i64) -> i64 {
+        return initial_amount_cents;
+    }
+
+    /// Stake of the next round. Only called once the bet has been won or lost.
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64;
+
+    /// Forgets the progression so far, e.g. after hitting the table maximum.
+    fn reset(&mut self) {}
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use super::*;
+
+    pub fn create_context(bet_state: BetState, amount_cents: i64) -> StrategyContext<'static> {
+        return StrategyContext {
+            bet_state,
+            bet_logs: &[],
+            amount_cents,
+            initial_amount_cents: 100,
+            progression_factor: 2,
+            balance_cents: 100000,
+            game_logs: &[],
+        };
+    }
+
+    /// Plays `outcomes` (true for a win) and returns the stake after each one
+    pub fn play_outcomes(strategy: &mut dyn Strategy, outcomes: &[bool]) -> Vec<i64> {
+        let mut amount_cents = strategy.opening_amount_cents(100);
+        let mut amounts = Vec::new();
+        for won in outcomes {
+            let bet_state = if *won { BetState::Won } else { BetState::Lost };
+            let bet_log = BetLog {
+                round_number: amounts.len() as i32 + 1,
+                amount_cents,
+                bet_state,
+                payout_cents: if *won { amount_cents * 2 } else { 0 },
+                table_limit_hit: false,
+            };
+            let bet_logs = [bet_log];
+            let context = StrategyContext {
+                bet_logs: &bet_logs,
+                ..create_context(bet_state, amount_cents)
+            };
+            amount_cents = strategy.next_amount_cents(&context);
+            amounts.push(amount_cents);
+        }
+        return amounts;
+    }
+}
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+use super::{
+    d_alembert::DAlembert,
+    fibonacci::Fibonacci,
+    grand_martingale::GrandMartingale,
+    labouchere::Labouchere,
+    martingale::Martingale,
+    one_three_two_six::OneThreeTwoSix,
+    oscars_grind::OscarsGrind,
+    paroli::Paroli,
+    strategy::{Strategy, StrategyContext},
+};
+
+/// Betting strategy of a single bet, together with its progression state.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum StrategyType {
+    DAlembert(DAlembert),
+    Fibonacci(Fibonacci),
+    GrandMartingale(GrandMartingale),
+    Labouchere(Labouchere),
+    Martingale(Martingale),
+    OneThreeTwoSix(OneThreeTwoSix),
+    OscarsGrind(OscarsGrind),
+    Paroli(Paroli),
+}
+
+impl Default for StrategyType {
+    fn default() -> Self {
+        return StrategyType::Martingale(Martingale::default());
+    }
+}
+
+impl StrategyType {
+    const NAME: &'static str = "StrategyType";
+
+    pub fn get_name(&self) -> String {
+        return String::from(match self {
+            StrategyType::DAlembert(_) => "D'Alembert",
+            StrategyType::Fibonacci(_) => "Fibonacci",
+            StrategyType::GrandMartingale(_) => "Grand Martingale",
+            StrategyType::Labouchere(_) => "Labouchere",
+            StrategyType::Martingale(_) => "Martingale",
+            StrategyType::OneThreeTwoSix(_) => "1-3-2-6",
+            StrategyType::OscarsGrind(_) => "Oscar's Grind",
+            StrategyType::Paroli(_) => "Paroli",
+        });
+    }
+
+    fn from_name(name: &str, value: &Value) -> Result<Self, Error> {
+        return match name {
+            "DAlembert" => Ok(StrategyType::DAlembert(DAlembert::default())),
+            "Fibonacci" => Ok(StrategyType::Fibonacci(Fibonacci::default())),
+            "GrandMartingale" => Ok(StrategyType::GrandMartingale(GrandMartingale::default())),
+            "Labouchere" => Ok(StrategyType::Labouchere(Labouchere::default())),
+            "Martingale" => Ok(StrategyType::Martingale(Martingale::default())),
+            "OneThreeTwoSix" => Ok(StrategyType::OneThreeTwoSix(OneThreeTwoSix::default())),
+            "OscarsGrind" => Ok(StrategyType::OscarsGrind(OscarsGrind::default())),
+            "Paroli" => Ok(StrategyType::Paroli(Paroli::default())),
+            _ => Err(Error::DeserializatonError {
+                message: format!("Invalid strategy: {}", name),
+                de_str: None,
+                value: Some(value.clone()),
+                nested_error: None,
+            }),
+        };
+    }
+
+    fn labouchere_from_value(sequence: &Value) -> Result<Self, Error> {
+        let error = Error::DeserializatonError {
+            message: "Labouchere sequence must be a non-empty array of positive integers"
+                .to_string(),
+            de_str: None,
+            value: Some(sequence.clone()),
+            nested_error: None,
+        };
+        let entries = match sequence.as_array() {
+            Some(entries) if !entries.is_empty() => entries,
+            _ => return Err(error),
+        };
+        let mut units: Vec<i64> = Vec::new();
+        for entry in entries {
+            match entry.as_i64() {
+                Some(unit) if unit > 0 => units.push(unit),
+                _ => return Err(error),
+            }
+        }
+        return Ok(StrategyType::Labouchere(Labouchere::new(units)));
+    }
+}
+
+impl Strategy for StrategyType {
+    fn opening_amount_cents(&self, initial_amount_cents: i64) -> i64 {
+        return match self {
+            StrategyType::DAlembert(strategy) => {
+                strategy.opening_amount_cents(initial_amount_cents)
+            }
+            StrategyType::Fibonacci(strategy) => {
+                strategy.opening_amount_cents(initial_amount_cents)
+            }
+            StrategyType::GrandMartingale(strategy) => {
+                strategy.opening_amount_cents(initial_amount_cents)
+            }
+            StrategyType::Labouchere(strategy) => {
+                strategy.opening_amount_cents(initial_amount_cents)
+            }
+            StrategyType::Martingale(strategy) => {
+                strategy.opening_amount_cents(initial_amount_cents)
+            }
+            StrategyType::OneThreeTwoSix(strategy) => {
+                strategy.opening_amount_cents(initial_amount_cents)
+            }
+            StrategyType::OscarsGrind(strategy) => {
+                strategy.opening_amount_cents(initial_amount_cents)
+            }
+            StrategyType::Paroli(strategy) => strategy.opening_amount_cents(initial_amount_cents),
+        };
+    }
+
+    fn next_amount_cents(&mut self, context: &StrategyContext) -> i64 {
+        return match self {
+            StrategyType::DAlembert(strategy) => strategy.next_amount_cents(context),
+            StrategyType::Fibonacci(strategy) => strategy.next_amount_cents(context),
+            StrategyType::GrandMartingale(strategy) => strategy.next_amount_cents(context),
+            StrategyType::Labouchere(strategy) => strategy.next_amount_cents(context),
+            StrategyType::Martingale(strategy) => strategy.next_amount_cents(context),
+            StrategyType::OneThreeTwoSix(strategy) => strategy.next_amount_cents(context),
+            StrategyType::OscarsGrind(strategy) => strategy.next_amount_cents(context),
+            StrategyType::Paroli(strategy) => strategy.next_amount_cents(context),
+        };
+    }
+
+    fn reset(&mut self) {
+        match self {
+            StrategyType::DAlembert(strategy) => strategy.reset(),
+            StrategyType::Fibonacci(strategy) => strategy.reset(),
+            StrategyType::GrandMartingale(strategy) => strategy.reset(),
+            StrategyType::Labouchere(strategy) => strategy.reset(),
+            StrategyType::Martingale(strategy) => strategy.reset(),
+            StrategyType::OneThreeTwoSix(strategy) => strategy.reset(),
+            StrategyType::OscarsGrind(strategy) => strategy.reset(),
+            StrategyType::Paroli(strategy) => strategy.reset(),
+        }
+    }
+}
+
+/// Accepts either a strategy name, e.g. `"Fibonacci"`, or an object for
+/// strategies taking parameters, e.g. `{"Labouchere": [1, 2, 3]}`.
+impl TryFrom<Value> for StrategyType {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        if let Some(name) = value.as_str() {
+            return Self::from_name(name, &value);
+        }
+        let val_obj = value.as_object().ok_or(Error::DeserializatonError {
+            message: format!(
+                "Value passed onto {}::try_from is neither a string nor an object",
+                Self::NAME
+            ),
+            de_str: None,
+            value: Some(value.clone()),
+            nested_error: None,
+        })?;
+        let (name, parameters) = val_obj.iter().next().ok_or(Error::DeserializatonError {
+            message: format!(
+                "Value passed onto {}::try_from is an empty object",
+                Self::NAME
+            ),
+            de_str: None,
+            value: Some(value.clone()),
+            nested_error: None,
+        })?;
+        return match name.as_str() {
+            "Labouchere" => Self::labouchere_from_value(parameters),
+            _ => Self::from_name(name, &value),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_try_from_name() {
+        assert_eq!(
+            StrategyType::try_from(json!("Fibonacci")).unwrap(),
+            StrategyType::Fibonacci(Fibonacci::default())
+        );
+        assert_eq!(
+            StrategyType::try_from(json!("OneThreeTwoSix")).unwrap(),
+            StrategyType::OneThreeTwoSix(OneThreeTwoSix::default())
+        );
+        assert_eq!(
+            StrategyType::try_from(json!({"Paroli": null})).unwrap(),
+            StrategyType::Paroli(Paroli::default())
+        );
+    }
+
+    #[test]
+    fn test_try_from_labouchere_sequence() {
+        assert_eq!(
+            StrategyType::try_from(json!({"Labouchere": [1, 1, 2]})).unwrap(),
+            StrategyType::Labouchere(Labouchere::new(vec![1, 1, 2]))
+        );
+        assert!(StrategyType::try_from(json!({"Labouchere": []})).is_err());
+        assert!(StrategyType::try_from(json!({"Labouchere": [1, -2]})).is_err());
+    }
+
+    #[test]
+    fn test_try_from_invalid() {
+        assert!(StrategyType::try_from(json!("Reverse Martingale")).is_err());
+        assert!(StrategyType::try_from(json!(3)).is_err());
+        assert!(StrategyType::try_from(json!({})).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use core::fmt;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::{board::wheel::Wheel, error::Error, json::deserializable::StringDeserializable};
+
+use super::slot_number::SlotNumber;
+
+/// Chips a call bet puts on a group of numbers laid out on the table
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Placement {
+    pub numbers: Vec<SlotNumber>,
+    pub chips: i64,
+}
+
+/// French racetrack bets, each spreading its stake over the splits,
+/// straights and corners that make up a sector of the European wheel
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
+pub enum CallBet {
+    VoisinsDuZero,
+    TiersDuCylindre,
+    Orphelins,
+    JeuZero,
+}
+
+impl CallBet {
+    pub fn get_placements(&self) -> Vec<Placement> {
+        let placements: &[(&[SlotNumber], i64)] = match self {
+            CallBet::VoisinsDuZero => &[
+                (&[0, 2, 3], 2),
+                (&[4, 7], 1),
+                (&[12, 15], 1),
+                (&[18, 21], 1),
+                (&[19, 22], 1),
+                (&[32, 35], 1),
+                (&[25, 26, 28, 29], 2),
+            ],
+            CallBet::TiersDuCylindre => &[
+                (&[5, 8], 1),
+                (&[10, 11], 1),
+                (&[13, 16], 1),
+                (&[23, 24], 1),
+                (&[27, 30], 1),
+                (&[33, 36], 1),
+            ],
+            CallBet::Orphelins => &[
+                (&[1], 1),
+                (&[6, 9], 1),
+                (&[14, 17], 1),
+                (&[17, 20], 1),
+                (&[31, 34], 1),
+            ],
+            CallBet::JeuZero => &[(&[0, 3], 1), (&[12, 15], 1), (&[32, 35], 1), (&[26], 1)],
+        };
+        return placements
+            .iter()
+            .map(|(numbers, chips)| Placement {
+                numbers: numbers.to_vec(),
+                chips: *chips,
+            })
+            .collect();
+    }
+
+    /// Chips it takes to place the bet once
+    pub fn get_chip_count(&self) -> i64 {
+        return self
+            .get_placements()
+            .iter()
+            .map(|placement| placement.chips)
+            .sum();
+    }
+
+    /// First and last pocket, clockwise, of the wheel sectors the bet covers
+    fn get_sectors(&self) -> &'static [(SlotNumber, SlotNumber)] {
+        return match self {
+            CallBet::VoisinsDuZero => &[(22, 25)],
+            CallBet::TiersDuCylindre => &[(27, 33)],
+            CallBet::Orphelins => &[(1, 9), (17, 6)],
+            CallBet::JeuZero => &[(12, 15)],
+        };
+    }
+
+    /// Whether the numbers the placements cover are exactly the bet's sectors of `wheel`
+    pub fn follows_wheel(&self, wheel: &Wheel) -> bool {
+        let mut sector_numbers: Vec<SlotNumber> = Vec::new();
+        for (first, last) in self.get_sectors().iter() {
+            match wheel.sector(*first, *last) {
+                Ok(numbers) => sector_numbers.extend(numbers),
+                Err(_) => return false,
+            }
+        }
+        let covered_numbers: Vec<SlotNumber> = self
+            .get_placements()
+            .into_iter()
+            .flat_map(|placement| placement.numbers)
+            .unique()
+            .sorted()
+            .collect();
+        sector_numbers.sort();
+        return sector_numbers == covered_numbers;
+    }
+}
+
+impl fmt::Display for CallBet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{}",
+            match self {
+                CallBet::VoisinsDuZero => "Voisins du Zéro",
+                CallBet::TiersDuCylindre => "Tiers du Cylindre",
+                CallBet::Orphelins => "Orphelins",
+                CallBet::JeuZero => "Jeu Zéro",
+            }
+        );
+    }
+}
+
+impl FromStr for CallBet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "VoisinsDuZero" | "Voisins du Zéro" => Ok(CallBet::VoisinsDuZero),
+            "TiersDuCylindre" | "Tiers du Cylindre" => Ok(CallBet::TiersDuCylindre),
+            "Orphelins" => Ok(CallBet::Orphelins),
+            "JeuZero" | "Jeu Zéro" => Ok(CallBet::JeuZero),
+            _ => Err(Error::FromStrError {
+                message: format!("Failed to convert {} to {}", s, Self::NAME),
+                string: s.to_string(),
+                nested_error: None,
+            }),
+        }
+    }
+}
+
+impl TryFrom<Value> for CallBet {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        return Self::try_deserialize(value);
+    }
+}
+
+impl StringDeserializable for CallBet {
+    const NAME: &'static str = "CallBet";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roulette::roulette_type::RouletteType;
+    use serde_json::json;
+
+    #[test]
+    fn test_try_from() {
+        let value = json!("VoisinsDuZero");
+        assert_eq!(CallBet::try_from(value).unwrap(), CallBet::VoisinsDuZero);
+        assert_eq!(CallBet::from_str("Jeu Zéro").unwrap(), CallBet::JeuZero);
+        assert!(CallBet::from_str("Neighbours").is_err());
+    }
+
+    #[test]
+    fn test_chip_counts() {
+        assert_eq!(CallBet::VoisinsDuZero.get_chip_count(), 9);
+        assert_eq!(CallBet::TiersDuCylindre.get_chip_count(), 6);
+        assert_eq!(CallBet::Orphelins.get_chip_count(), 5);
+        assert_eq!(CallBet::JeuZero.get_chip_count(), 4);
+    }
+
+    #[test]
+    fn test_follows_wheel() {
+        let european = Wheel::new(&RouletteType::European);
+        let american = Wheel::new(&RouletteType::American);
+        for call_bet in [
+            CallBet::VoisinsDuZero,
+            CallBet::TiersDuCylindre,
+            CallBet::Orphelins,
+            CallBet::JeuZero,
+        ] {
+            assert!(call_bet.follows_wheel(&european), "{}", call_bet);
+            assert!(!call_bet.follows_wheel(&american), "{}", call_bet);
+        }
+    }
+}
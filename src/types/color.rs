@@ -5,6 +5,12 @@ use std::str::FromStr;
 
 use crate::{error::Error, json::deserializable::StringDeserializable};
 
+use super::slot_number::SlotNumber;
+
+const RED_NUMBERS: [SlotNumber; 18] = [
+    1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36,
+];
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
 pub enum Color {
     Green,
@@ -51,6 +57,27 @@ impl TryFrom<Value> for Color {
     }
 }
 
+impl TryFrom<SlotNumber> for Color {
+    type Error = Error;
+
+    fn try_from(n: SlotNumber) -> Result<Self, Self::Error> {
+        return match n {
+            -1..=0 => Ok(Color::Green),
+            1..=36 => {
+                if RED_NUMBERS.contains(&n) {
+                    Ok(Color::Red)
+                } else {
+                    Ok(Color::Black)
+                }
+            }
+            _ => Err(Error::GenericError {
+                message: format!("{} is not a valid slot number", n),
+                nested_error: None,
+            }),
+        };
+    }
+}
+
 impl StringDeserializable for Color {
     const NAME: &'static str = "Row";
 }
@@ -70,4 +97,15 @@ mod tests {
     fn test_from_str() {
         assert_eq!(Color::from_str("Red").unwrap(), Color::Red);
     }
+
+    #[test]
+    fn test_try_from_slot_number() {
+        assert_eq!(Color::try_from(0).unwrap(), Color::Green);
+        assert_eq!(Color::try_from(-1).unwrap(), Color::Green);
+        assert_eq!(Color::try_from(1).unwrap(), Color::Red);
+        assert_eq!(Color::try_from(2).unwrap(), Color::Black);
+        assert_eq!(Color::try_from(10).unwrap(), Color::Black);
+        assert_eq!(Color::try_from(19).unwrap(), Color::Red);
+        assert!(Color::try_from(37).is_err());
+    }
 }
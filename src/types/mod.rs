@@ -1,4 +1,5 @@
 pub mod adjacent_numbers;
+pub mod call_bet;
 pub mod color;
 pub mod column;
 pub mod double_column;
@@ -134,4 +134,4 @@ mod tests {
         assert_eq!(Row::try_from(2).unwrap(), Row::Two);
         assert_eq!(Row::try_from(3).unwrap(), Row::Three);
     }
-}
\ No newline at end of file
+}